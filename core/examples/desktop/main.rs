@@ -1,4 +1,22 @@
 /// Finds the Game Boy on the primary display and shows the result in the terminal.
+///
+/// If the screen can not be located automatically, select its region with
+/// `--region <x>,<y>,<width>,<height>`. The validated region is saved to
+/// `calibration.json` and used in the following runs as well.
+///
+/// A custom font, e.g. of a ROM hack, is loaded with `--font <path>`.
+///
+/// The screens are scanned within a session lasting until the app is closed,
+/// e.g. the species of nicknamed Pokémon are known from their summaries.
+///
+/// The box lists of Bill's PC are scanned with `--box`. The box is put
+/// together from the scans and saved to `box.json`.
+///
+/// The bag is scanned with `--bag`. The TMs and HMs are shown with the party
+/// members learning them, as shown on the party menu scanned before.
+///
+/// The badges can not be read from the RBY trainer card. To show the stats
+/// boosted by the badges in battle, give them with `--badges Boulder,Soul`.
 pub mod screen_capturer;
 
 use crossterm::{
@@ -7,9 +25,37 @@ use crossterm::{
     ExecutableCommand, Result,
 };
 use image::DynamicImage;
-use std::{io::stdout, time::Instant};
+use std::{fs, io::stdout, time::Instant};
 
 use core as pkmn;
+use pkmn::calibration::Calibration;
+use pkmn::gameboy::{parse_badges, RBY_BADGES};
+use pkmn::ocr::CharTable;
+use pkmn::position::Position;
+use pkmn::session::ScanSession;
+use pkmn::utils::ScanOptions;
+
+const CALIBRATION_PATH: &str = "calibration.json";
+const BOX_PATH: &str = "box.json";
+
+/// Parses the value of the `--region` argument.
+fn parse_region(text: &str) -> Option<Position> {
+    let values: Vec<u32> = text
+        .split(',')
+        .map(|x| x.trim().parse::<u32>())
+        .collect::<std::result::Result<_, _>>()
+        .ok()?;
+
+    match values[..] {
+        [x, y, width, height] => Some(Position {
+            x,
+            y,
+            width,
+            height,
+        }),
+        _ => None,
+    }
+}
 
 fn main() -> Result<()> {
     let mut stdout = stdout();
@@ -20,6 +66,67 @@ fn main() -> Result<()> {
         panic!("There was an error in capturing the primary display.");
     };
 
+    let args: Vec<String> = std::env::args().collect();
+    let region = args
+        .iter()
+        .position(|arg| arg == "--region")
+        .map(|idx| args.get(idx + 1).and_then(|x| parse_region(x)));
+
+    let mut options = ScanOptions::default();
+    match region {
+        Some(Some(pos)) => {
+            // Select region step
+            let img_screen = capturer.next_frame();
+            let Ok(img_screen) = img_screen else {
+                panic!("There was an error retrieving the display frame.")
+            };
+            let img_screen = DynamicImage::ImageRgb8(img_screen.clone());
+
+            let calibration =
+                pkmn::calibration::calibrate(&img_screen, &pos, &options.aspect_models);
+            let calibration = match calibration {
+                Ok(calibration) => calibration,
+                Err(error) => panic!("The selected region is invalid: {error}"),
+            };
+
+            fs::write(CALIBRATION_PATH, calibration.to_json())?;
+            options = ScanOptions::from_calibration(&calibration);
+        }
+        Some(None) => panic!("Expected the region as --region <x>,<y>,<width>,<height>"),
+        None => {
+            if let Ok(text) = fs::read_to_string(CALIBRATION_PATH) {
+                match Calibration::from_json(&text) {
+                    Ok(calibration) => options = ScanOptions::from_calibration(&calibration),
+                    Err(error) => panic!("Could not load '{CALIBRATION_PATH}': {error}"),
+                }
+            }
+        }
+    }
+
+    if let Some(idx) = args.iter().position(|arg| arg == "--font") {
+        let Some(path) = args.get(idx + 1) else {
+            panic!("Expected the font as --font <path>");
+        };
+        let text = fs::read_to_string(path)?;
+        match CharTable::from_json(&text) {
+            Ok(font) => options.font = Some(font),
+            Err(error) => panic!("Could not load '{path}': {error}"),
+        }
+    }
+
+    let is_box = args.iter().any(|arg| arg == "--box");
+    let is_bag = args.iter().any(|arg| arg == "--bag");
+
+    let mut session = ScanSession::new();
+    if let Some(idx) = args.iter().position(|arg| arg == "--badges") {
+        let Some(text) = args.get(idx + 1) else {
+            panic!("Expected the badges as --badges <badge>,<badge>,...");
+        };
+        match parse_badges(text, &RBY_BADGES) {
+            Ok(badges) => session.record_badges(&RBY_BADGES, &badges),
+            Err(error) => panic!("Could not set the badges: {error}"),
+        }
+    }
     loop {
         let img_screen = capturer.next_frame();
         let Ok(img_screen) = img_screen else {
@@ -28,9 +135,19 @@ fn main() -> Result<()> {
         let img_screen = DynamicImage::ImageRgb8(img_screen.clone());
 
         let start = Instant::now();
-        let scan_result = pkmn::utils::scan_img(img_screen);
+        let scan_result = if is_box {
+            pkmn::utils::scan_box_with_session(img_screen, &options, &mut session)
+        } else if is_bag {
+            pkmn::utils::scan_bag_with_session(img_screen, &options, &session)
+        } else {
+            pkmn::utils::scan_img_with_session(img_screen, &options, &mut session)
+        };
         let duration = start.elapsed();
 
+        if is_box && scan_result.is_ok() {
+            fs::write(BOX_PATH, session.box_inventory().to_json())?;
+        }
+
         let text_output = match scan_result {
             Ok(text_output) => text_output,
             Err(error) => error,
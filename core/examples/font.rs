@@ -0,0 +1,26 @@
+//! Creates a font file from a glyph sheet.
+//!
+//! Usage: `cargo run --example font -- <image> <glyph sheet JSON> <output JSON>`
+//!
+//! The created font can be loaded by the desktop app with `--font <path>`.
+
+use core::ocr::{CharTable, GlyphSheet};
+use std::fs;
+
+fn main() -> Result<(), String> {
+    let args: Vec<String> = std::env::args().collect();
+    let [_, img_path, sheet_path, font_path] = &args[..] else {
+        return Err("expected arguments <image> <glyph sheet JSON> <output JSON>".to_string());
+    };
+
+    let img = image::open(img_path).map_err(|err| format!("could not open image: {err}"))?;
+    let sheet = fs::read_to_string(sheet_path)
+        .map_err(|err| format!("could not read glyph sheet: {err}"))?;
+    let sheet = GlyphSheet::from_json(&sheet)?;
+
+    let chars = CharTable::from_glyph_sheet(&img, &sheet)?;
+    fs::write(font_path, chars.to_json()).map_err(|err| format!("could not write font: {err}"))?;
+
+    println!("Saved {} characters to '{font_path}'", chars.len());
+    Ok(())
+}
@@ -1,6 +1,6 @@
 //! A dev app used in creating the Game Boy screen-locating functionality.
 
-use core::gameboy::{search_screen_gsc, search_screen_rby};
+use core::gameboy::{search_screen_gsc, search_screen_rby, AspectModel};
 use image::{io::Reader as ImageReader, Luma};
 use imageproc::{contrast::threshold_mut, rect::Rect};
 use show_image::{self};
@@ -90,7 +90,7 @@ fn main() -> Result<(), String> {
 
             print!("  #{i}: ");
 
-            let screen_candidates = search_screen_rby(&contours);
+            let screen_candidates = search_screen_rby(&contours, &AspectModel::native());
             let rby_screen_pos = screen_candidates.iter().max_by_key(|&p| p.width);
             if let Some(screen_pos) = rby_screen_pos {
                 let rect = Rect::at(screen_pos.x as i32, screen_pos.y as i32)
@@ -106,7 +106,7 @@ fn main() -> Result<(), String> {
                 );
             }
 
-            let screen_candidates = search_screen_gsc(&contours);
+            let screen_candidates = search_screen_gsc(&contours, &AspectModel::native());
             let gsc_screen_pos = screen_candidates.iter().max_by_key(|&p| p.width);
             if let Some(screen_pos) = gsc_screen_pos {
                 let height = screen_pos.width as f32 * 144.0 / 160.0;
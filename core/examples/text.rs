@@ -0,0 +1,31 @@
+//! Prints the lines of text found on a screenshot, with their positions.
+//!
+//! Usage: `cargo run --example text -- <image>`
+//!
+//! Helps with finding the positions of the fields of a new screen.
+
+use core::gameboy::{locate_screen_with, AspectModel};
+use core::ocr::CharTable;
+use core::palette::normalize_palette;
+use core::text::detect_text;
+
+fn main() -> Result<(), String> {
+    let args: Vec<String> = std::env::args().collect();
+    let [_, img_path] = &args[..] else {
+        return Err("expected argument <image>".to_string());
+    };
+
+    let img = image::open(img_path).map_err(|err| format!("could not open image: {err}"))?;
+    let (pos, model) = locate_screen_with(&img, &AspectModel::defaults())
+        .ok_or("could not locate the Game Boy screen")?;
+    let img = normalize_palette(&model.to_native(&img, &pos));
+
+    for line in detect_text(&img, &CharTable::new()) {
+        let pos = line.pos;
+        println!(
+            "({:>3}, {:>3}) {:>3}x{}  {}",
+            pos.x, pos.y, pos.width, pos.height, line.text
+        );
+    }
+    Ok(())
+}
@@ -207,6 +207,27 @@ def get_learnset_leveling_up(markdown_source: str) -> list[list[str]]:
     return table
 
 
+def get_learnset_tm_hm(markdown_source: str) -> list[list[str]]:
+    """Extracts the "By TM/HM" learnset table from the WIKI markdown source.
+
+    The returned table contains the header and the rows with the TM or HM and
+    the move. Pokemon learning no TM or HM have an empty table.
+    """
+
+    rows = []
+    for row in markdown_source.splitlines():
+        if not row.startswith("{{learnlist/tm"):
+            continue  # Not part of the "By TM/HM" table
+
+        row_elems = row.removesuffix("}}").split("|")
+        if len(row_elems) < 3 or not row_elems[1].startswith(("TM", "HM")):
+            continue  # Header, footer or the row of no TMs and HMs
+
+        rows.append(row_elems[1:3])
+
+    return [["TM/HM", "Move"]] + rows
+
+
 def norm_learnset_table(table: list[list[str]]) -> list[list[str]]:
     """If present, splits the Level column into RGB and Y columns."""
     raise NotImplementedError("Only works for Gen 1")
@@ -267,6 +288,7 @@ def scrape_gen_learnset(gen: str, category_url: str) -> None:
             "ndex": ndex.strip("0"),
             "pokemon": pokemon,
             "by_leveling_up": table,
+            "by_tm_hm": get_learnset_tm_hm(markdown_source),
         }
         pkmn_entries.append(entry)
 
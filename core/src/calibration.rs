@@ -0,0 +1,136 @@
+//! Manual selection of the Game Boy screen region.
+//!
+//! When the screen can not be located automatically, e.g. due to overlays on
+//! a stream, the region of the screen can be provided by the user instead.
+//! The region is validated against the known layouts and can be persisted as
+//! JSON, so it only needs to be selected once.
+
+use crate::gameboy::AspectModel;
+use crate::ocr::CharTable;
+use crate::palette::normalize_palette;
+use crate::position::Position;
+use crate::screen::ScreenClassifier;
+use crate::tilemap::TileMap;
+use image::DynamicImage;
+
+/// A validated region of the Game Boy screen on the captured image.
+#[derive(Debug, Copy, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Calibration {
+    /// The position of the visible screen on the image.
+    pub pos: Position,
+    /// The way the screen is stretched or cropped within the region.
+    pub model: AspectModel,
+}
+
+impl Calibration {
+    /// Serializes the calibration into JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("failed to serialize calibration")
+    }
+
+    /// Deserializes the calibration from JSON.
+    pub fn from_json(text: &str) -> Result<Calibration, String> {
+        serde_json::from_str(text).map_err(|err| format!("could not parse calibration: {err}"))
+    }
+}
+
+/// Validates the user-provided screen region against the known layouts.
+///
+/// The region is mapped to the native screen with each of the aspect models,
+/// starting with the one closest to its aspect ratio. The first model that
+/// results in a recognized layout is used for the calibration.
+pub fn calibrate(
+    img: &DynamicImage,
+    pos: &Position,
+    models: &[AspectModel],
+) -> Result<Calibration, String> {
+    let (w, h) = (img.width(), img.height());
+    let fits = pos.end().is_some_and(|(x1, y1)| x1 <= w && y1 <= h);
+    if pos.width == 0 || pos.height == 0 || !fits {
+        return Err(format!(
+            "region {pos:?} does not fit into the image of {w}x{h} pixels"
+        ));
+    }
+
+    let chars = CharTable::new();
+    let classifier = ScreenClassifier::new();
+
+    for model in AspectModel::sorted_by_ratio(models, pos) {
+        let img_gameboy = model.to_native(img, pos);
+        let img_gameboy = normalize_palette(&img_gameboy);
+
+        let tilemap = TileMap::read(&img_gameboy, &chars)?;
+
+        if classifier.classify(&tilemap).is_some() {
+            return Ok(Calibration { pos: *pos, model });
+        }
+    }
+
+    Err("could not recognize screen layout within the selected region".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{scan_img, scan_img_with, ScanOptions};
+    use image::imageops::overlay;
+    use image::{Rgba, RgbaImage};
+
+    const IMG: &[u8] = include_bytes!("../data/images/Yellow_summary_2.png");
+
+    #[test]
+    fn calibrate_and_scan_region() {
+        let img = image::load_from_memory(IMG).unwrap();
+        let expected = scan_img(img.clone()).expect("could not scan original");
+
+        // An all-white surrounding hides the screen border
+        let mut framed = RgbaImage::from_pixel(200, 180, Rgba([255, 255, 255, 255]));
+        overlay(&mut framed, &img.to_rgba8(), 20, 16);
+        let framed = DynamicImage::ImageRgba8(framed);
+        assert!(scan_img(framed.clone()).is_err());
+
+        let pos = Position {
+            x: 20,
+            y: 16,
+            width: 160,
+            height: 144,
+        };
+        let calibration = calibrate(&framed, &pos, &AspectModel::defaults()).unwrap();
+        let calibration = Calibration::from_json(&calibration.to_json()).unwrap();
+
+        let result = scan_img_with(framed, &ScanOptions::from_calibration(&calibration));
+        assert_eq!(result, Ok(expected));
+    }
+
+    #[test]
+    fn calibrate_overflowing_region() {
+        let img = image::load_from_memory(IMG).unwrap();
+        let pos = Position {
+            x: u32::MAX,
+            y: 0,
+            width: 160,
+            height: 144,
+        };
+
+        assert!(calibrate(&img, &pos, &AspectModel::defaults()).is_err());
+
+        let options = ScanOptions {
+            screen_pos: Some(pos),
+            ..ScanOptions::default()
+        };
+        assert!(scan_img_with(img, &options).is_err());
+    }
+
+    #[test]
+    fn calibrate_wrong_region() {
+        let img = image::load_from_memory(IMG).unwrap();
+        let pos = Position {
+            x: 0,
+            y: 0,
+            width: 80,
+            height: 72,
+        };
+
+        assert!(calibrate(&img, &pos, &AspectModel::defaults()).is_err());
+    }
+}
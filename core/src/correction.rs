@@ -0,0 +1,200 @@
+//! Correction of misread names.
+//!
+//! A single misread character makes a move, item or species name unknown.
+//! As these names come from a fixed list, the read text is snapped to the
+//! closest valid name. Characters with similar glyphs, like "O" and "Q", are
+//! more likely to be confused, so replacing one with the other costs less.
+
+use crate::ocr::CharTable;
+use std::collections::HashMap;
+
+/// The Hamming distance above which two glyphs are considered unrelated.
+const UNRELATED_DIST: u32 = 8;
+
+/// The highest accepted cost of a correction per character of the name.
+const MAX_COST_PER_CHAR: f32 = 0.25;
+
+/// The costs of reading a character as another, based on their glyphs.
+pub struct ConfusionCosts {
+    costs: HashMap<(char, char), f32>,
+}
+
+impl ConfusionCosts {
+    /// Derives the costs from the glyphs of the character table.
+    ///
+    /// Characters consisting of multiple letters, like the "PK" ligature,
+    /// are left out.
+    pub fn new(chars: &CharTable) -> ConfusionCosts {
+        let glyphs: Vec<_> = chars
+            .iter()
+            .filter_map(|(bitmap, text)| {
+                let mut text_chars = text.chars();
+                match (text_chars.next(), text_chars.next()) {
+                    (Some(char), None) => Some((bitmap, char)),
+                    _ => None,
+                }
+            })
+            .collect();
+
+        let mut costs = HashMap::new();
+        for &(bitmap_a, a) in &glyphs {
+            for &(bitmap_b, b) in &glyphs {
+                if a == b {
+                    continue;
+                }
+                let dist = bitmap_a.hamming_dist(bitmap_b).min(UNRELATED_DIST);
+                let cost = dist as f32 / UNRELATED_DIST as f32;
+                let min_cost = costs.entry((a, b)).or_insert(cost);
+                *min_cost = f32::min(*min_cost, cost);
+            }
+        }
+
+        ConfusionCosts { costs }
+    }
+
+    /// Returns the cost of reading the `valid` character as `read`, between 0 and 1.
+    pub fn substitution(&self, read: char, valid: char) -> f32 {
+        if read == valid {
+            return 0.0;
+        }
+        self.costs.get(&(read, valid)).copied().unwrap_or(1.0)
+    }
+
+    /// Returns the cheapest way of turning the valid text into the read one.
+    ///
+    /// Inserting or removing a character costs 1.
+    pub fn distance(&self, read: &str, valid: &str) -> f32 {
+        let read: Vec<char> = read.chars().collect();
+        let valid: Vec<char> = valid.chars().collect();
+
+        let mut prev_row: Vec<f32> = (0..=valid.len()).map(|x| x as f32).collect();
+        for (i, &read_char) in read.iter().enumerate() {
+            let mut row = vec![(i + 1) as f32; valid.len() + 1];
+            for (j, &valid_char) in valid.iter().enumerate() {
+                let substitution = prev_row[j] + self.substitution(read_char, valid_char);
+                let insertion = row[j] + 1.0;
+                let deletion = prev_row[j + 1] + 1.0;
+                row[j + 1] = substitution.min(insertion).min(deletion);
+            }
+            prev_row = row;
+        }
+
+        prev_row[valid.len()]
+    }
+}
+
+/// A read text snapped to a valid name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Correction {
+    /// The text as read from the screen.
+    pub read: String,
+    /// The closest valid name.
+    pub name: String,
+    /// The cost of turning the name into the read text.
+    pub cost: f32,
+    /// The confidence of the correction, between 0 and 1.
+    ///
+    /// It is 0.5 when another name is just as close.
+    pub confidence: f32,
+}
+
+impl Correction {
+    /// Returns true if the read text differs from the name.
+    pub fn is_corrected(&self) -> bool {
+        self.read != self.name
+    }
+}
+
+/// A list of valid names, like those of the moves.
+pub struct Dictionary {
+    names: Vec<String>,
+    costs: ConfusionCosts,
+}
+
+impl Dictionary {
+    /// Creates a dictionary of the names in upper case, as shown in the game.
+    pub fn new<'a>(names: impl IntoIterator<Item = &'a str>, chars: &CharTable) -> Dictionary {
+        let mut names: Vec<String> = names.into_iter().map(|x| x.to_uppercase()).collect();
+        names.sort();
+        names.dedup();
+
+        Dictionary {
+            names,
+            costs: ConfusionCosts::new(chars),
+        }
+    }
+
+    /// Returns the valid name closest to the read text.
+    ///
+    /// Returns None if even the closest name differs too much.
+    pub fn correct(&self, read: &str) -> Option<Correction> {
+        let read = read.trim();
+        if self.names.iter().any(|name| name == read) {
+            return Some(Correction {
+                read: read.to_string(),
+                name: read.to_string(),
+                cost: 0.0,
+                confidence: 1.0,
+            });
+        }
+
+        let mut costs: Vec<(f32, &String)> = self
+            .names
+            .iter()
+            .map(|name| (self.costs.distance(read, name), name))
+            .collect();
+        costs.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let &(cost, name) = costs.first()?;
+        let max_cost = MAX_COST_PER_CHAR * name.chars().count() as f32;
+        if cost > max_cost {
+            return None;
+        }
+
+        let confidence = match costs.get(1) {
+            Some(&(runner_up_cost, _)) => runner_up_cost / (cost + runner_up_cost),
+            None => 1.0,
+        };
+
+        Some(Correction {
+            read: read.to_string(),
+            name: name.clone(),
+            cost,
+            confidence,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::moves::GscMoves;
+
+    #[test]
+    fn similar_glyphs_cost_less() {
+        let costs = ConfusionCosts::new(&CharTable::new());
+
+        assert!(costs.substitution('Q', 'O') < costs.substitution('X', 'O'));
+        assert_eq!(costs.substitution('O', 'O'), 0.0);
+        assert_eq!(costs.distance("TACKLE", "TACKLE"), 0.0);
+        assert_eq!(costs.distance("TACKL", "TACKLE"), 1.0);
+    }
+
+    #[test]
+    fn correct_move_name() {
+        let chars = CharTable::new();
+        let moves = GscMoves::new();
+        let dictionary = Dictionary::new(moves.names(), &chars);
+
+        let correction = dictionary.correct("SCRATCH").unwrap();
+        assert!(!correction.is_corrected());
+        assert_eq!(correction.confidence, 1.0);
+
+        let correction = dictionary.correct("SCRATCM").unwrap();
+        assert!(correction.is_corrected());
+        assert_eq!(correction.name, "SCRATCH");
+        assert!(correction.confidence > 0.5 && correction.confidence < 1.0);
+
+        assert!(dictionary.correct("XXXXXXXX").is_none());
+    }
+}
@@ -0,0 +1,289 @@
+//! Messages of the dialog text box.
+//!
+//! Battle and overworld messages are printed in the text box at the bottom
+//! of the screen, two lines at a time. Recognized messages are turned into
+//! events, like a Pokémon growing a level.
+
+use crate::tilemap::{TileMap, UNKNOWN_CHAR};
+
+/// The maximum length of the names of the Pokémon, nicknames included.
+const NAME_LEN: usize = 10;
+
+/// The maximum length of the names of the moves.
+const MOVE_NAME_LEN: usize = 12;
+
+/// The layout of the dialog text box of RBY and GSC.
+///
+/// The text box spans the tile rows 12 to 17. Its two lines of text start
+/// after the left border.
+pub struct DialogBox {
+    pub col: u32,
+    pub rows: [u32; 2],
+    /// The number of characters per line.
+    pub len: u32,
+}
+
+impl DialogBox {
+    /// Creates a new instance of the dialog text box layout.
+    pub fn new() -> DialogBox {
+        DialogBox {
+            col: 1,
+            rows: [14, 16],
+            len: 18,
+        }
+    }
+
+    /// Reads the two lines of the text box.
+    ///
+    /// The tile map is used rather than [`crate::ocr::read_field`], as the
+    /// mixed case text has descenders, like "g", which reach into the row
+    /// below the other characters. Unknown tiles, like the arrow prompting
+    /// for the next message, are removed from the end of the lines.
+    pub fn read_lines(&self, tilemap: &TileMap) -> [String; 2] {
+        self.rows.map(|row| {
+            tilemap
+                .text(self.col, row, self.len)
+                .trim_end_matches([' ', UNKNOWN_CHAR])
+                .trim_start()
+                .to_string()
+        })
+    }
+
+    /// Reads the message of the text box as a single line.
+    pub fn read_text(&self, tilemap: &TileMap) -> String {
+        let lines = self.read_lines(tilemap);
+        lines
+            .iter()
+            .filter(|line| !line.is_empty())
+            .cloned()
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+}
+
+impl Default for DialogBox {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An event told by a message of the text box.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DialogEvent {
+    /// A wild Pokémon appeared or a trainer sent out a Pokémon.
+    Encounter { species: String, wild: bool },
+    /// A Pokémon grew to the level.
+    LevelUp { name: String, level: i32 },
+    /// A Pokémon learned the move.
+    MoveLearned { name: String, move_name: String },
+    /// A Pokémon evolved into the species.
+    Evolution { name: String, species: String },
+    /// A Pokémon fainted, either of the player or of the opponent.
+    Fainted { name: String, opponent: bool },
+    /// The last move was super effective.
+    SuperEffective,
+    /// The last move was not very effective.
+    NotVeryEffective,
+}
+
+impl DialogEvent {
+    /// Returns the event told by the message, if any.
+    ///
+    /// Expects the message as a single line, see [`DialogBox::read_text`].
+    /// Each pattern must match the whole message, so a message telling
+    /// something else, or several messages read at once, are not mistaken
+    /// for an event. The more specific patterns are tried first.
+    pub fn parse(text: &str) -> Option<DialogEvent> {
+        let text = text.trim();
+
+        if text == "It's super effective!" {
+            return Some(DialogEvent::SuperEffective);
+        }
+        if let Some(dots) = text.strip_prefix("It's not very effective") {
+            if dots.chars().all(|c| c == '.' || c == '…') {
+                return Some(DialogEvent::NotVeryEffective);
+            }
+            return None;
+        }
+
+        if let Some(species) = text
+            .strip_prefix("Wild ")
+            .and_then(|x| x.strip_suffix(" appeared!"))
+        {
+            return is_species(species).then(|| DialogEvent::Encounter {
+                species: species.to_string(),
+                wild: true,
+            });
+        }
+
+        // GSC congratulates the player first
+        let evolution = text.strip_prefix("Congratulations! ").unwrap_or(text);
+        if let Some((name, species)) = split_message(evolution, " evolved into ") {
+            let name = name.strip_prefix("Your ").unwrap_or(name);
+            return (is_name(name) && is_species(species)).then(|| DialogEvent::Evolution {
+                name: name.to_string(),
+                species: species.to_string(),
+            });
+        }
+
+        if let Some((name, level)) = split_message(text, " grew to level ") {
+            if !is_name(name) || !level.chars().all(|c| c.is_ascii_digit()) {
+                return None;
+            }
+            return Some(DialogEvent::LevelUp {
+                name: name.to_string(),
+                level: level.parse::<i32>().ok()?,
+            });
+        }
+
+        if let Some((name, move_name)) = split_message(text, " learned ") {
+            let is_move = move_name.chars().count() <= MOVE_NAME_LEN;
+            return (is_name(name) && is_move).then(|| DialogEvent::MoveLearned {
+                name: name.to_string(),
+                move_name: move_name.to_string(),
+            });
+        }
+
+        if let Some((_, species)) = split_message(text, " sent out ") {
+            return is_species(species).then(|| DialogEvent::Encounter {
+                species: species.to_string(),
+                wild: false,
+            });
+        }
+
+        if let Some(name) = text.strip_suffix(" fainted!") {
+            let opponent_name = name
+                .strip_prefix("Enemy ")
+                .or_else(|| name.strip_prefix("Wild "));
+            let name = opponent_name.unwrap_or(name);
+            return is_name(name).then(|| DialogEvent::Fainted {
+                name: name.to_string(),
+                opponent: opponent_name.is_some(),
+            });
+        }
+
+        None
+    }
+}
+
+/// Splits a message of the form "<before><infix><after>!".
+///
+/// The infix must occur once, and neither part may be empty or end a
+/// sentence itself.
+fn split_message<'a>(text: &'a str, infix: &str) -> Option<(&'a str, &'a str)> {
+    let text = text.strip_suffix('!')?;
+    if text.matches(infix).count() != 1 {
+        return None;
+    }
+    let (before, after) = text.split_once(infix)?;
+    let is_part = |part: &str| !part.is_empty() && !part.contains('!');
+    (is_part(before) && is_part(after)).then_some((before, after))
+}
+
+/// Returns true if the text can be the name of a Pokémon.
+fn is_name(text: &str) -> bool {
+    !text.is_empty() && text.chars().count() <= NAME_LEN && !text.contains('!')
+}
+
+/// Returns true if the text can be the name of a species, a single word.
+fn is_species(text: &str) -> bool {
+    is_name(text) && !text.contains(' ')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ocr::{draw_text, CharTable};
+    use image::GrayImage;
+
+    #[test]
+    fn read_dialog_box() {
+        let chars = CharTable::new();
+        let mut img = GrayImage::new(160, 144);
+        draw_text(&mut img, "Wild PIDGEY", 8, 112, &chars);
+        draw_text(&mut img, "appeared!", 8, 128, &chars);
+
+        let tilemap = TileMap::read(&img, &chars).unwrap();
+        let dialog = DialogBox::new();
+        assert_eq!(dialog.read_lines(&tilemap), ["Wild PIDGEY", "appeared!"]);
+
+        let text = dialog.read_text(&tilemap);
+        assert_eq!(
+            DialogEvent::parse(&text),
+            Some(DialogEvent::Encounter {
+                species: "PIDGEY".to_string(),
+                wild: true,
+            })
+        );
+    }
+
+    #[test]
+    fn parse_messages() {
+        assert_eq!(
+            DialogEvent::parse("PIKACHU grew to level 12!"),
+            Some(DialogEvent::LevelUp {
+                name: "PIKACHU".to_string(),
+                level: 12,
+            })
+        );
+        assert_eq!(
+            DialogEvent::parse("PIKACHU learned THUNDERBOLT!"),
+            Some(DialogEvent::MoveLearned {
+                name: "PIKACHU".to_string(),
+                move_name: "THUNDERBOLT".to_string(),
+            })
+        );
+        assert_eq!(
+            DialogEvent::parse("Congratulations! Your TOTODILE evolved into CROCONAW!"),
+            Some(DialogEvent::Evolution {
+                name: "TOTODILE".to_string(),
+                species: "CROCONAW".to_string(),
+            })
+        );
+        assert_eq!(
+            DialogEvent::parse("Enemy PIDGEY fainted!"),
+            Some(DialogEvent::Fainted {
+                name: "PIDGEY".to_string(),
+                opponent: true,
+            })
+        );
+        assert_eq!(
+            DialogEvent::parse("BROCK sent out ONIX!"),
+            Some(DialogEvent::Encounter {
+                species: "ONIX".to_string(),
+                wild: false,
+            })
+        );
+        assert_eq!(
+            DialogEvent::parse("It's super effective!"),
+            Some(DialogEvent::SuperEffective)
+        );
+        assert_eq!(DialogEvent::parse("PIKACHU used SLAM!"), None);
+    }
+
+    #[test]
+    fn parse_whole_messages_only() {
+        let messages = [
+            // Encounter
+            "Wild PIDGEY fled!",
+            "YOUNGSTER sent out RATTATA that learned TACKLE!",
+            // LevelUp
+            "PIKACHU grew to level 12 and learned SLAM!",
+            "The trainer of PIKACHU grew to level 12!",
+            // MoveLearned
+            "Poof! PIKACHU forgot TACKLE! PIKACHU learned SLAM!",
+            "PIKACHU did not learn THUNDERBOLT.",
+            // Evolution
+            "PIKACHU evolved into RAICHU and CLEFAIRY!",
+            // Fainted
+            "Enemy PIDGEY used TACKLE! PIKACHU fainted!",
+            // SuperEffective
+            "It's super effective! Enemy PIDGEY fainted!",
+            // NotVeryEffective
+            "It's not very effective! Enemy PIDGEY fainted!",
+        ];
+        for message in messages {
+            assert_eq!(DialogEvent::parse(message), None, "{message}");
+        }
+    }
+}
@@ -1,8 +1,9 @@
 //! Functionality to format data
 
-use crate::learnset::Learnset;
+use crate::correction::Correction;
+use crate::learnset::{Learnset, LevelUpMove};
 use crate::moves::{GscMoves, Move, Moves};
-use crate::stats::DvRange;
+use crate::stats::{DvRange, GrowthRate, StatVariation, MAX_LEVEL};
 
 /// Returns the header of the stat table.
 pub fn fmt_stat_header() -> String {
@@ -22,6 +23,103 @@ pub fn fmt_stat_row(stat: &str, base_stat: &i32, stat_value: &i32, dv_range: &Dv
     h
 }
 
+/// Returns the base stats of a species, one per row.
+pub fn fmt_base_stats(stats: &[(&str, i32)]) -> String {
+    let mut t = String::from("Base stats\n");
+    for (stat, base_stat) in stats {
+        t.push_str(&format!("{:>4}  {:>4}\n", stat, base_stat));
+    }
+    t
+}
+
+/// Returns the header of the stat table of the opponent in battle.
+///
+/// The stats of trainers' Pokémon are shown only if their DVs are known.
+pub fn fmt_battle_stat_header(has_trainer_dvs: bool) -> String {
+    let mut t = format!("{:>4}  {:>4}  {:>4}  {:>4}", "Stat", "Base", "Min", "Max");
+    if has_trainer_dvs {
+        t.push_str("  Trainer");
+    }
+    t.push('\n');
+    t
+}
+
+/// Returns a row of the stat table of the opponent in battle.
+///
+/// The stat of a trainer's Pokémon is given by the DV of trainers, if known.
+pub fn fmt_battle_stat_row(
+    stat: &str,
+    base_stat: &i32,
+    variation: &StatVariation,
+    trainer_dv: Option<i32>,
+) -> String {
+    let range = variation.range();
+    let mut t = format!(
+        "{:>4}  {:>4}  {:>4}  {:>4}",
+        stat, base_stat, range.min, range.max
+    );
+    if let Some(dv) = trainer_dv {
+        t.push_str(&format!("  {:>7}", variation[dv as usize]));
+    }
+    t.push('\n');
+    t
+}
+
+/// Returns the types super effective against a Pokémon, with their multiplier.
+pub fn fmt_weaknesses(weaknesses: &[(&str, f32)]) -> String {
+    let weaknesses: Vec<String> = weaknesses
+        .iter()
+        .map(|(type_, multiplier)| format!("{type_} x{multiplier}"))
+        .collect();
+    match weaknesses.is_empty() {
+        true => "Weak to: -\n".to_string(),
+        false => format!("Weak to: {}\n", weaknesses.join(", ")),
+    }
+}
+
+/// Returns the note about a corrected name.
+pub fn fmt_correction(correction: &Correction) -> String {
+    format!(
+        "Read '{}' as '{}' ({:.0}% confidence)\n",
+        correction.read,
+        correction.name,
+        correction.confidence * 100.0
+    )
+}
+
+/// Returns the EXP points and the progress towards the next level.
+pub fn fmt_exp_progress(
+    level: i32,
+    exp: i32,
+    exp_to_next: i32,
+    growth_rate: &GrowthRate,
+) -> String {
+    if level >= MAX_LEVEL {
+        return format!("EXP {exp}, max level reached\n");
+    }
+
+    let level_exp = growth_rate.exp(level);
+    let next_exp = growth_rate.exp(level + 1);
+    let progress = (exp - level_exp) as f32 / (next_exp - level_exp) as f32;
+    format!(
+        "EXP {exp}, {exp_to_next} to L{} ({:.0}% of level)\n",
+        level + 1,
+        progress.clamp(0.0, 1.0) * 100.0
+    )
+}
+
+/// Returns the level-up moves, one per line.
+pub fn fmt_level_up_moves(moves: &[LevelUpMove]) -> String {
+    let mut t = String::new();
+    for move_ in moves {
+        if let Some(version) = &move_.version {
+            t.push_str(&format!("{version:<3}  "));
+        }
+        t.push_str(&format!("L{:<3}  {}\n", move_.level, move_.name));
+    }
+    t
+}
+
 pub fn fmt_move_header() -> String {
     format!(
         "{:<15}  {:<8}  {:<12}  {:>3}  {:>4}  {:>2}  {}",
@@ -1,79 +1,288 @@
+use crate::dialog::{DialogBox, DialogEvent};
 use crate::ocr::{read_char, read_field, CharTable};
+use crate::palette::normalize_palette;
+use crate::pokemon::{Gender, RbyPokemon};
 use crate::position::Position;
-use image::{DynamicImage, GrayImage, Luma};
+use crate::roi::Roi;
+use crate::stats::{hp_from_bar, StatRange, StatVariation, HP_BAR_PIXELS, MAX_LEVEL};
+use crate::tilemap::{tile_field, TileMap, TILE_SIZE};
+use image::imageops::{invert, overlay};
+use image::{DynamicImage, GrayImage, Luma, Rgba, RgbaImage};
 use imageproc::contours::Contour;
-use imageproc::contrast::threshold_mut;
+use imageproc::contrast::{otsu_level, threshold_mut};
+use std::collections::HashMap;
 
-/// Returns the possible RBY screen positions.
-///
-/// # Notes:
-///
-/// The all-white border of the RBY summary screen could prevent the border to
-/// be found. As a workaround, add a black padding around, or a dummy black pixel within the border.
-pub fn search_screen_rby(contours: &Vec<Contour<i32>>) -> Vec<Position> {
-    let width_orig = 160;
-    let height_orig = 144;
+/// The width of the native Game Boy screen in pixels.
+const SCREEN_WIDTH: u32 = 160;
 
-    // Look for rectangle
-    let target_ratio = width_orig as f32 / height_orig as f32;
-    let tolerance = 0.01;
+/// The height of the native Game Boy screen in pixels.
+const SCREEN_HEIGHT: u32 = 144;
+
+/// Describes how the native 160x144 Game Boy screen appears on an image.
+///
+/// Emulators and the GBA can stretch the screen to a different aspect ratio,
+/// and capture setups may crop a few pixels from its edges.
+#[derive(Debug, Copy, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct AspectModel {
+    /// The width of a native pixel relative to its height.
+    pub pixel_aspect: f32,
+    /// The number of native pixels cropped from the left, top, right and bottom.
+    pub crop: [u32; 4],
+    /// The accepted deviation from the expected aspect ratio.
+    pub tolerance: f32,
+}
 
-    let mut candidates: Vec<Position> = Vec::with_capacity(8);
-    for contour in contours {
-        let bbox = Position::try_from(contour).expect("could not create Position");
+impl AspectModel {
+    /// The unscaled 160:144 screen.
+    pub fn native() -> AspectModel {
+        AspectModel {
+            pixel_aspect: 1.0,
+            crop: [0, 0, 0, 0],
+            tolerance: 0.01,
+        }
+    }
 
-        if bbox.width < width_orig || bbox.height < height_orig {
-            continue; // Smaller than original resolution
+    /// The screen stretched to the 240x160 GBA display.
+    pub fn gba_stretch() -> AspectModel {
+        AspectModel {
+            pixel_aspect: (240.0 / 160.0) / (160.0 / 144.0),
+            crop: [0, 0, 0, 0],
+            tolerance: 0.01,
         }
+    }
 
-        let ratio = bbox.width as f32 / bbox.height as f32;
-        if (ratio - target_ratio).abs() > tolerance {
-            continue; // Not within tolerance
+    /// The screen with the given number of native pixels cropped from its edges.
+    pub fn cropped(left: u32, top: u32, right: u32, bottom: u32) -> AspectModel {
+        AspectModel {
+            pixel_aspect: 1.0,
+            crop: [left, top, right, bottom],
+            tolerance: 0.01,
         }
+    }
 
-        candidates.push(bbox);
+    /// Returns the models tried when no other model is specified.
+    pub fn defaults() -> Vec<AspectModel> {
+        vec![AspectModel::native(), AspectModel::gba_stretch()]
     }
-    candidates
-}
 
-/// Searches and returns the possible screen positions for Pokemon GSC.
-pub fn search_screen_gsc(contours: &Vec<Contour<i32>>) -> Vec<Position> {
-    let width_orig = 160;
-    let height_orig = 62;
+    /// Returns the aspect ratio of the visible screen on the image.
+    pub fn ratio(&self) -> f32 {
+        let visible = self.visible_region();
+        visible.width as f32 * self.pixel_aspect / visible.height as f32
+    }
 
-    // Look for rectangle
-    let target_ratio = width_orig as f32 / height_orig as f32;
-    let tolerance = 0.01;
+    /// Returns the models ordered by how close their aspect ratio is to that
+    /// of the region.
+    pub fn sorted_by_ratio(models: &[AspectModel], pos: &Position) -> Vec<AspectModel> {
+        let ratio = pos.width as f32 / pos.height as f32;
+        let mut models = models.to_vec();
+        models.sort_by(|a, b| {
+            let diff_a = (a.ratio() - ratio).abs();
+            let diff_b = (b.ratio() - ratio).abs();
+            diff_a.total_cmp(&diff_b)
+        });
+        models
+    }
+
+    /// Returns the model with the aspect ratio closest to that of the region.
+    pub fn closest(models: &[AspectModel], pos: &Position) -> Option<AspectModel> {
+        AspectModel::sorted_by_ratio(models, pos).first().copied()
+    }
+
+    /// Returns the visible part of the native screen.
+    pub fn visible_region(&self) -> Position {
+        let [left, top, right, bottom] = self.crop;
+        Position {
+            x: left,
+            y: top,
+            width: SCREEN_WIDTH.saturating_sub(left + right),
+            height: SCREEN_HEIGHT.saturating_sub(top + bottom),
+        }
+    }
 
-    let mut candidates: Vec<Position> = Vec::with_capacity(8);
-    for contour in contours {
-        let mut bbox = Position::try_from(contour).expect("could not create Position");
+    /// Returns the possible positions of the visible screen on the image.
+    ///
+    /// The target is a region of the native screen, that is expected to form
+    /// a contour on the binary image, such as the all-white RBY summary screen.
+    fn search_screen(&self, contours: &Vec<Contour<i32>>, target: &Position) -> Vec<Position> {
+        let visible = self.visible_region();
 
-        if bbox.width < width_orig || bbox.height < height_orig {
-            continue; // Smaller than original resolution
+        // The part of the target that is not cropped
+        let x0 = target.x.max(visible.x);
+        let y0 = target.y.max(visible.y);
+        let x1 = (target.x + target.width).min(visible.x + visible.width);
+        let y1 = (target.y + target.height).min(visible.y + visible.height);
+        if x1 <= x0 || y1 <= y0 {
+            return Vec::new();
         }
+        let (width_orig, height_orig) = (x1 - x0, y1 - y0);
+
+        // Look for rectangle
+        let target_ratio = width_orig as f32 * self.pixel_aspect / height_orig as f32;
+
+        let mut candidates: Vec<Position> = Vec::with_capacity(8);
+        for contour in contours {
+            let bbox = Position::try_from(contour).expect("could not create Position");
+
+            if bbox.width < width_orig || bbox.height < height_orig {
+                continue; // Smaller than original resolution
+            }
+
+            let ratio = bbox.width as f32 / bbox.height as f32;
+            if (ratio - target_ratio).abs() > self.tolerance {
+                continue; // Not within tolerance
+            }
+
+            // Extrapolate visible screen position
+            let scale_x = bbox.width as f32 / width_orig as f32;
+            let scale_y = scale_x / self.pixel_aspect;
+            let offset_x = ((x0 - visible.x) as f32 * scale_x) as u32;
+            let offset_y = ((y0 - visible.y) as f32 * scale_y) as u32;
+            if offset_x > bbox.x || offset_y > bbox.y {
+                continue; // Screen would start outside of the image
+            }
+
+            let height = if y0 == visible.y && y1 == visible.y + visible.height {
+                bbox.height // The target spans the whole visible height
+            } else {
+                (visible.height as f32 * scale_y) as u32
+            };
 
-        let ratio = bbox.width as f32 / bbox.height as f32;
-        if (ratio - target_ratio).abs() > tolerance {
-            continue; // Not within tolerance
+            candidates.push(Position {
+                x: bbox.x - offset_x,
+                y: bbox.y - offset_y,
+                width: (visible.width as f32 * scale_x) as u32,
+                height,
+            });
         }
+        candidates
+    }
+
+    /// Maps the visible screen on the image back to the native 160x144 grid.
+    ///
+    /// The cropped edges are filled with the most common color of the screen,
+    /// which is expected to be the background.
+    pub fn to_native(&self, img: &DynamicImage, pos: &Position) -> DynamicImage {
+        let visible = self.visible_region();
 
-        // Extrapolate full screen position
-        let estimated_height = bbox.width as f32 * 144.0 / 160.0;
-        let estimated_height = estimated_height as u32;
+        let img_visible = img
+            .crop_imm(pos.x, pos.y, pos.width, pos.height)
+            .resize_exact(
+                visible.width,
+                visible.height,
+                image::imageops::FilterType::Nearest,
+            );
 
-        bbox.height = estimated_height;
+        if visible.width == SCREEN_WIDTH && visible.height == SCREEN_HEIGHT {
+            return img_visible;
+        }
+
+        let img_visible = img_visible.to_rgba8();
+        let mut histogram = HashMap::<[u8; 4], u32>::new();
+        for pixel in img_visible.pixels() {
+            *histogram.entry(pixel.0).or_insert(0) += 1;
+        }
+        let background = histogram
+            .into_iter()
+            .max_by_key(|&(color, count)| (count, color))
+            .map(|(color, _)| Rgba(color))
+            .unwrap_or(Rgba([255, 255, 255, 255]));
 
-        candidates.push(bbox);
+        let mut img_native = RgbaImage::from_pixel(SCREEN_WIDTH, SCREEN_HEIGHT, background);
+        overlay(
+            &mut img_native,
+            &img_visible,
+            visible.x as i64,
+            visible.y as i64,
+        );
+        DynamicImage::ImageRgba8(img_native)
     }
-    candidates
+}
+
+/// Returns the possible RBY screen positions.
+///
+/// # Notes:
+///
+/// The all-white border of the RBY summary screen could prevent the border to
+/// be found. As a workaround, add a black padding around, or a dummy black pixel within the border.
+pub fn search_screen_rby(contours: &Vec<Contour<i32>>, model: &AspectModel) -> Vec<Position> {
+    let target = Position {
+        x: 0,
+        y: 0,
+        width: SCREEN_WIDTH,
+        height: SCREEN_HEIGHT,
+    };
+    model.search_screen(contours, &target)
+}
+
+/// Searches and returns the possible screen positions for Pokemon GSC.
+///
+/// Looks for the white area at the top of the summary screens and
+/// extrapolates the full screen position from it.
+pub fn search_screen_gsc(contours: &Vec<Contour<i32>>, model: &AspectModel) -> Vec<Position> {
+    let target = Position {
+        x: 0,
+        y: 0,
+        width: SCREEN_WIDTH,
+        height: 62,
+    };
+    model.search_screen(contours, &target)
 }
 
 /// Returns the position of the biggest Game Boy screen on the image.
 ///
 /// Works with the Summary screens of RBY and GSC.
+/// Only looks for screens with the native aspect ratio.
 pub fn locate_screen(img: &DynamicImage) -> Option<Position> {
-    let img = img.to_luma8();
+    locate_screen_with(img, &[AspectModel::native()]).map(|(pos, _)| pos)
+}
+
+/// Returns the position of the biggest Game Boy screen and its aspect model.
+///
+/// Works with the Summary screens of RBY and GSC.
+/// Palettes with a dark lightest shade, inverted palettes and palettes with
+/// similar luma for the text and the background are handled by retrying with
+/// differently binarized images.
+pub fn locate_screen_with(
+    img: &DynamicImage,
+    models: &[AspectModel],
+) -> Option<(Position, AspectModel)> {
+    let img_luma = img.to_luma8();
+
+    // Can be set in a wide range
+    // [30, 240] for RBY and GSC
+    // [190, 230] for RBY and GS with Super Game Boy Border
+    let threshold_val = 200;
+    let mut img_binary = img_luma.clone();
+    threshold_mut(&mut img_binary, threshold_val);
+    if let Some(found) = locate_screen_binary(&img_binary, models) {
+        return Some(found);
+    }
+
+    let threshold_val = otsu_level(&img_luma);
+    let mut img_binary = img_luma;
+    threshold_mut(&mut img_binary, threshold_val);
+    if let Some(found) = locate_screen_binary(&img_binary, models) {
+        return Some(found);
+    }
+
+    invert(&mut img_binary);
+    if let Some(found) = locate_screen_binary(&img_binary, models) {
+        return Some(found);
+    }
+
+    let mut img_binary = normalize_palette(img);
+    invert(&mut img_binary); // The screen background should be white
+    locate_screen_binary(&img_binary, models)
+}
+
+/// Returns the position of the biggest Game Boy screen on the binary image.
+///
+/// The screen background is expected to be white.
+fn locate_screen_binary(
+    img: &GrayImage,
+    models: &[AspectModel],
+) -> Option<(Position, AspectModel)> {
     let pixels_orig = img.as_raw();
 
     let (w_old, h_old) = (img.width(), img.height());
@@ -95,12 +304,6 @@ pub fn locate_screen(img: &DynamicImage) -> Option<Position> {
     let mut img =
         GrayImage::from_raw(w_new, h_new, pixels_bordered).expect("failed to add border to image");
 
-    // Can be set in a wide range
-    // [30, 240] for RBY and GSC
-    // [190, 230] for RBY and GS with Super Game Boy Border
-    let threshold_val = 200;
-    threshold_mut(&mut img, threshold_val);
-
     // find_contours() does not find the border on an all-white image.
     // Add black marker pixel as a work-around.
     *img.get_pixel_mut_checked(0, 0)
@@ -108,24 +311,33 @@ pub fn locate_screen(img: &DynamicImage) -> Option<Position> {
 
     let contours = imageproc::contours::find_contours::<i32>(&img);
 
-    let rby_candidates = search_screen_rby(&contours);
-    let gsc_candidates = search_screen_gsc(&contours);
+    let mut candidates: Vec<(Position, AspectModel)> = Vec::new();
+    for model in models {
+        let gsc_candidates = search_screen_gsc(&contours, model);
+        let rby_candidates = search_screen_rby(&contours, model);
+        candidates.extend(
+            gsc_candidates
+                .into_iter()
+                .chain(rby_candidates)
+                .map(|p| (p, *model)),
+        );
+    }
 
-    let biggest = gsc_candidates
-        .iter()
-        .chain(rby_candidates.iter())
-        .max_by_key(|&p| p.width * p.height);
+    let biggest = candidates
+        .into_iter()
+        .filter(|(p, _)| p.x >= border_width && p.y >= border_width)
+        .max_by_key(|(p, _)| p.width * p.height);
 
     match biggest {
-        Some(a) => {
+        Some((a, model)) => {
             // Undo border offset
             let pos_orig = Position {
                 x: a.x - border_width,
                 y: a.y - border_width,
-                width: a.width,
-                height: a.height,
+                width: a.width.min(w_old + border_width - a.x),
+                height: a.height.min(h_old + border_width - a.y),
             };
-            Some(pos_orig)
+            Some((pos_orig, model))
         }
         None => None,
     }
@@ -142,6 +354,12 @@ pub struct RbySummary1 {
     pub defense_field_pos: Position,
     pub speed_field_pos: Position,
     pub special_field_pos: Position,
+    pub nickname_field_pos: Position,
+    pub status_field_pos: Position,
+    pub type1_field_pos: Position,
+    pub type2_field_pos: Position,
+    pub id_field_pos: Position,
+    pub ot_field_pos: Position,
     pub slash_positions: [Position; 4],
 }
 
@@ -217,6 +435,42 @@ impl RbySummary1 {
                 width: field_width,
                 height: field_height,
             },
+            nickname_field_pos: Position {
+                x: 72,
+                y: 8,
+                width: 79, // 10 chars
+                height: field_height,
+            },
+            status_field_pos: Position {
+                x: 128,
+                y: 48,
+                width: 23, // "OK" or e.g. "PSN"
+                height: field_height,
+            },
+            type1_field_pos: Position {
+                x: 88,
+                y: 80,
+                width: 63, // 8 chars
+                height: field_height,
+            },
+            type2_field_pos: Position {
+                x: 88,
+                y: 96,
+                width: 63, // 8 chars, blank for a single type
+                height: field_height,
+            },
+            id_field_pos: Position {
+                x: 96,
+                y: 112,
+                width: 39, // 5 digits
+                height: field_height,
+            },
+            ot_field_pos: Position {
+                x: 96,
+                y: 128,
+                width: 55, // 7 chars
+                height: field_height,
+            },
             slash_positions: [
                 Position {
                     x: 120,
@@ -327,6 +581,19 @@ impl RbySummary1 {
             .parse::<i32>()
             .map_err(|_| format!("could not parse special '{special}' to i32"))?;
 
+        // The other fields are not needed for the DVs, so a field that can
+        // not be read, e.g. due to a missing glyph, is left out
+        let nickname = read_text(img, &self.nickname_field_pos, chars, "nickname").ok();
+        let status = read_text(img, &self.status_field_pos, chars, "status").ok();
+        let type1 = read_text(img, &self.type1_field_pos, chars, "type 1").ok();
+        let type2 = read_text(img, &self.type2_field_pos, chars, "type 2").ok();
+        let (type1, type2) = match type1.zip(type2) {
+            Some((type1, type2)) => (Some(type1), Some(type2).filter(|x| !x.is_empty())),
+            None => (None, None),
+        };
+        let id = read_number(img, &self.id_field_pos, chars, "ID").ok();
+        let ot = read_text(img, &self.ot_field_pos, chars, "OT").ok();
+
         let content = RbySummaryContent {
             ndex,
             level,
@@ -335,6 +602,12 @@ impl RbySummary1 {
             defense,
             speed,
             special,
+            nickname,
+            status,
+            type1,
+            type2,
+            id,
+            ot,
         };
         Ok(content)
     }
@@ -350,6 +623,37 @@ pub struct RbySummaryContent {
     pub defense: i32,
     pub speed: i32,
     pub special: i32,
+    /// The fields below are None if they could not be read.
+    pub nickname: Option<String>,
+    /// "OK" or the status condition, e.g. "PSN".
+    pub status: Option<String>,
+    pub type1: Option<String>,
+    /// None as well for the Pokémon with a single type.
+    pub type2: Option<String>,
+    pub id: Option<i32>,
+    pub ot: Option<String>,
+}
+
+impl RbySummaryContent {
+    /// Returns an error if the types differ from those of the Pokémon.
+    ///
+    /// As the Pokémon is looked up by its dex number, this catches a
+    /// misread dex number.
+    pub fn verify_types(&self, pokemon: &RbyPokemon) -> Result<(), String> {
+        let type1 = self.type1.as_ref().ok_or("could not read the types")?;
+        let expected_type2 = Some(pokemon.type2.to_uppercase()).filter(|x| !x.is_empty());
+        if *type1 != pokemon.type1.to_uppercase() || self.type2 != expected_type2 {
+            let types = match &self.type2 {
+                Some(type2) => format!("{type1}/{type2}"),
+                None => type1.clone(),
+            };
+            return Err(format!(
+                "types {types} do not match No.{} {}",
+                pokemon.ndex, pokemon.name
+            ));
+        }
+        Ok(())
+    }
 }
 
 /// The layout of the RBY summary screen 2.
@@ -361,6 +665,9 @@ pub struct RbySummary2 {
     pub move_2: Position,
     pub move_3: Position,
     pub move_4: Position,
+    pub exp_field_pos: Position,
+    pub exp_to_next_field_pos: Position,
+    pub next_level_field_pos: Position,
 }
 
 impl RbySummary2 {
@@ -405,6 +712,24 @@ impl RbySummary2 {
                 width: 95,
                 height: 7,
             },
+            exp_field_pos: Position {
+                x: 96,
+                y: 32,
+                width: 55, // 7 digits
+                height: field_height,
+            },
+            exp_to_next_field_pos: Position {
+                x: 56,
+                y: 48,
+                width: 55, // 7 digits
+                height: field_height,
+            },
+            next_level_field_pos: Position {
+                x: 136,
+                y: 48,
+                width: 15, // After the ":L" char
+                height: field_height,
+            },
         }
     }
 
@@ -470,12 +795,42 @@ impl RbySummary2 {
             .trim()
             .to_string();
 
+        let exp = read_field(img, &self.exp_field_pos, chars)
+            .map_err(|err| format!("could not read EXP points: {err}"))?;
+        let exp = exp
+            .trim()
+            .parse::<i32>()
+            .map_err(|_| format!("could not parse EXP points '{exp}' to i32"))?;
+
+        let exp_to_next = read_field(img, &self.exp_to_next_field_pos, chars)
+            .map_err(|err| format!("could not read EXP to next level: {err}"))?;
+        let exp_to_next = exp_to_next
+            .trim()
+            .parse::<i32>()
+            .map_err(|_| format!("could not parse EXP to next level '{exp_to_next}' to i32"))?;
+
+        let next_level = read_field(img, &self.next_level_field_pos, chars)
+            .map_err(|err| format!("could not read next level: {err}"))?;
+        let next_level = next_level
+            .trim()
+            .parse::<i32>()
+            .map_err(|_| format!("could not parse next level '{next_level}' to i32"))?;
+        // Level 100 replaces the ":L" char, leaving "00" in the field
+        let next_level = if next_level == 0 {
+            MAX_LEVEL
+        } else {
+            next_level
+        };
+
         let content = RbySummaryContent2 {
             ndex,
             move_1,
             move_2,
             move_3,
             move_4,
+            exp,
+            exp_to_next,
+            next_level,
         };
         Ok(content)
     }
@@ -489,6 +844,109 @@ pub struct RbySummaryContent2 {
     pub move_2: String,
     pub move_3: String,
     pub move_4: String,
+    pub exp: i32,
+    pub exp_to_next: i32,
+    /// The level shown next to the EXP points to level up, 100 at most.
+    pub next_level: i32,
+}
+
+impl RbySummaryContent2 {
+    /// Returns the current level of the Pokémon.
+    pub fn level(&self) -> i32 {
+        match self.next_level {
+            MAX_LEVEL if self.exp_to_next == 0 => MAX_LEVEL,
+            next_level => next_level - 1,
+        }
+    }
+}
+
+/// The position of the gender symbol on the GSC summary screens.
+const GSC_GENDER_POS: Position = Position {
+    x: 144,
+    y: 0,
+    width: 7,
+    height: 7,
+};
+
+/// The position of the shiny star on the GSC summary screens.
+const GSC_SHINY_POS: Position = Position {
+    x: 152,
+    y: 0,
+    width: 7,
+    height: 7,
+};
+
+/// The fields shown at the top of every GSC summary screen.
+struct GscHeader {
+    ndex: i32,
+    level: i32,
+    /// None if the Pokémon is genderless or the symbol could not be read.
+    gender: Option<Gender>,
+    shiny: bool,
+}
+
+/// Reads the fields shown at the top of every GSC summary screen.
+///
+/// Only the ndex and level are required. The shiny star is only taken as
+/// such if it matches its glyph.
+fn read_gsc_header(
+    img: &GrayImage,
+    chars: &CharTable,
+    ndex: &Position,
+    level: &Position,
+    gender: &Position,
+    shiny: &Position,
+) -> Result<GscHeader, String> {
+    let ndex_text =
+        read_field(img, ndex, chars).map_err(|err| format!("could not read ndex: {err}"))?;
+    let ndex = ndex_text
+        .trim()
+        .parse::<i32>()
+        .map_err(|_| format!("could not parse ndex '{ndex_text}' to i32"))?;
+
+    let level_text =
+        read_field(img, level, chars).map_err(|err| format!("could not read level: {err}"))?;
+    let level = level_text
+        .trim()
+        .parse::<i32>()
+        .map_err(|_| format!("could not parse level '{level_text}' to i32"))?;
+
+    let gender = read_char(img, gender, chars)
+        .ok()
+        .and_then(|x| Gender::from_symbol(&x));
+
+    let shiny = read_char(img, shiny, chars).is_ok_and(|x| x == "★");
+
+    Ok(GscHeader {
+        ndex,
+        level,
+        gender,
+        shiny,
+    })
+}
+
+/// Reads a field of text, trimmed.
+fn read_text(
+    img: &GrayImage,
+    pos: &Position,
+    chars: &CharTable,
+    name: &str,
+) -> Result<String, String> {
+    let text =
+        read_field(img, pos, chars).map_err(|err| format!("could not read {name}: {err}"))?;
+    Ok(text.trim().to_string())
+}
+
+/// Reads a field of a number.
+fn read_number(
+    img: &GrayImage,
+    pos: &Position,
+    chars: &CharTable,
+    name: &str,
+) -> Result<i32, String> {
+    let text = read_text(img, pos, chars, name)?;
+    text.parse::<i32>()
+        .map_err(|_| format!("could not parse {name} '{text}' to i32"))
 }
 
 /// The layout of the GSC summary screen 1.
@@ -498,8 +956,17 @@ pub struct GscSummary1 {
 
     pub ndex: Position,
     pub level: Position,
+    pub gender: Position,
+    pub shiny: Position,
 
+    pub nickname: Position,
     pub hp: Position,
+    pub status: Position,
+    pub pokerus: Position,
+    pub type1: Position,
+    pub type2: Position,
+    pub exp: Position,
+    pub exp_to_next: Position,
 }
 
 impl GscSummary1 {
@@ -534,7 +1001,51 @@ impl GscSummary1 {
             height,
             ndex,
             level,
+            gender: GSC_GENDER_POS,
+            shiny: GSC_SHINY_POS,
+            nickname: Position {
+                x: 64,
+                y: 16,
+                width: 79, // 10 chars
+                height: 7,
+            },
             hp,
+            status: Position {
+                x: 48,
+                y: 104,
+                width: 23, // "OK" or e.g. "PSN"
+                height: 7,
+            },
+            pokerus: Position {
+                x: 8,
+                y: 104,
+                width: 23, // "PKRS", "PK" being a single char
+                height: 7,
+            },
+            type1: Position {
+                x: 8,
+                y: 120,
+                width: 63, // 8 chars
+                height: 7,
+            },
+            type2: Position {
+                x: 8,
+                y: 128,
+                width: 63, // 8 chars, blank for a single type
+                height: 7,
+            },
+            exp: Position {
+                x: 104,
+                y: 80,
+                width: 55, // 7 digits
+                height: 7,
+            },
+            exp_to_next: Position {
+                x: 104,
+                y: 104,
+                width: 55, // 7 digits
+                height: 7,
+            },
         };
 
         layout
@@ -565,6 +1076,77 @@ impl GscSummary1 {
         }
         true
     }
+
+    /// Reads the fields of the layout from the screen.
+    ///
+    /// Expects the image to be a binary image.
+    pub fn read_fields(
+        &self,
+        img: &GrayImage,
+        chars: &CharTable,
+    ) -> Result<GscSummaryContent, String> {
+        if img.width() as i32 != self.width || img.height() as i32 != self.height {
+            return Err("Mismatch in image and layout dimensions.".to_string());
+        }
+
+        let header = read_gsc_header(
+            img,
+            chars,
+            &self.ndex,
+            &self.level,
+            &self.gender,
+            &self.shiny,
+        )?;
+
+        // Only the HP is needed for the DVs, so a field that can not be
+        // read, e.g. due to a missing glyph, is left out
+        let type1 = read_text(img, &self.type1, chars, "type 1").ok();
+        let type2 = read_text(img, &self.type2, chars, "type 2").ok();
+        let (type1, type2) = match type1.zip(type2) {
+            Some((type1, type2)) => (Some(type1), Some(type2).filter(|x| !x.is_empty())),
+            None => (None, None),
+        };
+
+        let content = GscSummaryContent {
+            ndex: header.ndex,
+            level: header.level,
+            gender: header.gender,
+            shiny: header.shiny,
+            nickname: read_text(img, &self.nickname, chars, "nickname").ok(),
+            hp: read_number(img, &self.hp, chars, "hp")?,
+            status: read_text(img, &self.status, chars, "status").ok(),
+            pokerus: read_text(img, &self.pokerus, chars, "Pokérus")
+                .ok()
+                .map(|x| x == "PKRS"),
+            type1,
+            type2,
+            exp: read_number(img, &self.exp, chars, "EXP points").ok(),
+            exp_to_next: read_number(img, &self.exp_to_next, chars, "EXP to next level").ok(),
+        };
+        Ok(content)
+    }
+}
+
+/// The content of the GSC summary screen 1.
+#[derive(Debug, PartialEq, Clone)]
+pub struct GscSummaryContent {
+    pub ndex: i32,
+    pub level: i32,
+    /// None if the Pokémon is genderless or the symbol could not be read.
+    pub gender: Option<Gender>,
+    pub shiny: bool,
+    pub hp: i32,
+    /// The fields below are None if they could not be read.
+    pub nickname: Option<String>,
+    /// "OK" or the status condition, e.g. "PSN". Blank with Pokérus.
+    pub status: Option<String>,
+    /// True while the Pokémon is infected by Pokérus.
+    pub pokerus: Option<bool>,
+    pub type1: Option<String>,
+    /// None as well for the Pokémon with a single type.
+    pub type2: Option<String>,
+    pub exp: Option<i32>,
+    pub exp_to_next: Option<i32>,
 }
 
 /// The layout of the GSC summary screen 2.
@@ -574,6 +1156,8 @@ pub struct GscSummary2 {
 
     pub ndex: Position,
     pub level: Position,
+    pub gender: Position,
+    pub shiny: Position,
 
     pub item: Position,
 
@@ -601,6 +1185,8 @@ impl GscSummary2 {
                 width: 23,
                 height: 7,
             },
+            gender: GSC_GENDER_POS,
+            shiny: GSC_SHINY_POS,
             item: Position {
                 x: 64,
                 y: 64,
@@ -661,6 +1247,56 @@ impl GscSummary2 {
         }
         true
     }
+
+    /// Reads the fields of the layout from the screen.
+    ///
+    /// Expects the image to be a binary image.
+    pub fn read_fields(
+        &self,
+        img: &GrayImage,
+        chars: &CharTable,
+    ) -> Result<GscSummaryContent2, String> {
+        if img.width() as i32 != self.width || img.height() as i32 != self.height {
+            return Err("Mismatch in image and layout dimensions.".to_string());
+        }
+
+        let header = read_gsc_header(
+            img,
+            chars,
+            &self.ndex,
+            &self.level,
+            &self.gender,
+            &self.shiny,
+        )?;
+
+        let content = GscSummaryContent2 {
+            ndex: header.ndex,
+            level: header.level,
+            gender: header.gender,
+            shiny: header.shiny,
+            item: read_text(img, &self.item, chars, "item").ok(),
+            move_1: read_text(img, &self.move_1, chars, "move_1").ok(),
+            move_2: read_text(img, &self.move_2, chars, "move_2").ok(),
+            move_3: read_text(img, &self.move_3, chars, "move_3").ok(),
+            move_4: read_text(img, &self.move_4, chars, "move_4").ok(),
+        };
+        Ok(content)
+    }
+}
+
+/// The content of the GSC summary screen 2.
+#[derive(Debug, PartialEq, Clone)]
+pub struct GscSummaryContent2 {
+    pub ndex: i32,
+    pub level: i32,
+    pub gender: Option<Gender>,
+    pub shiny: bool,
+    /// The fields below are None if they could not be read.
+    pub item: Option<String>,
+    pub move_1: Option<String>,
+    pub move_2: Option<String>,
+    pub move_3: Option<String>,
+    pub move_4: Option<String>,
 }
 
 /// The layout of the GSC summary screen 3.
@@ -671,12 +1307,17 @@ pub struct GscSummary3 {
 
     pub ndex: Position,
     pub level: Position,
+    pub gender: Position,
+    pub shiny: Position,
 
     pub attack: Position,
     pub defense: Position,
     pub spc_attack: Position,
     pub spc_defense: Position,
     pub speed: Position,
+
+    pub id: Position,
+    pub ot: Position,
 }
 
 impl GscSummary3 {
@@ -697,6 +1338,8 @@ impl GscSummary3 {
                 width: 23,
                 height: 7,
             },
+            gender: GSC_GENDER_POS,
+            shiny: GSC_SHINY_POS,
             attack: Position {
                 x: 136,
                 y: 72,
@@ -727,6 +1370,18 @@ impl GscSummary3 {
                 width: 23,
                 height: 7,
             },
+            id: Position {
+                x: 16,
+                y: 80,
+                width: 39, // 5 digits
+                height: 7,
+            },
+            ot: Position {
+                x: 16,
+                y: 104,
+                width: 55, // 7 chars
+                height: 7,
+            },
         };
 
         layout
@@ -757,4 +1412,2870 @@ impl GscSummary3 {
         }
         true
     }
+
+    /// Reads the fields of the layout from the screen.
+    ///
+    /// Expects the image to be a binary image.
+    pub fn read_fields(
+        &self,
+        img: &GrayImage,
+        chars: &CharTable,
+    ) -> Result<GscSummaryContent3, String> {
+        if img.width() as i32 != self.width || img.height() as i32 != self.height {
+            return Err("Mismatch in image and layout dimensions.".to_string());
+        }
+
+        let header = read_gsc_header(
+            img,
+            chars,
+            &self.ndex,
+            &self.level,
+            &self.gender,
+            &self.shiny,
+        )?;
+
+        let content = GscSummaryContent3 {
+            ndex: header.ndex,
+            level: header.level,
+            gender: header.gender,
+            shiny: header.shiny,
+            attack: read_number(img, &self.attack, chars, "attack")?,
+            defense: read_number(img, &self.defense, chars, "defense")?,
+            spc_attack: read_number(img, &self.spc_attack, chars, "spc_attack")?,
+            spc_defense: read_number(img, &self.spc_defense, chars, "spc_defense")?,
+            speed: read_number(img, &self.speed, chars, "speed")?,
+            // Not needed for the DVs, None if it can not be read
+            id: read_number(img, &self.id, chars, "ID").ok(),
+            ot: read_text(img, &self.ot, chars, "OT").ok(),
+        };
+        Ok(content)
+    }
+}
+
+/// The content of the GSC summary screen 3.
+#[derive(Debug, PartialEq, Clone)]
+pub struct GscSummaryContent3 {
+    pub ndex: i32,
+    pub level: i32,
+    pub gender: Option<Gender>,
+    pub shiny: bool,
+    pub attack: i32,
+    pub defense: i32,
+    pub spc_attack: i32,
+    pub spc_defense: i32,
+    pub speed: i32,
+    /// None if it could not be read.
+    pub id: Option<i32>,
+    /// None if it could not be read.
+    pub ot: Option<String>,
+}
+
+/// The positions of the fields of a party menu slot.
+pub struct PartySlotPositions {
+    pub nickname: Position,
+    pub level: Position,
+    pub status: Position,
+    pub hp: Position,
+    pub max_hp: Position,
+    pub hp_bar: Position,
+}
+
+/// A Pokémon shown in the party menu.
+#[derive(Debug, PartialEq, Clone)]
+pub struct PartySlot {
+    pub nickname: String,
+    pub level: i32,
+    pub hp: i32,
+    pub max_hp: i32,
+    /// "OK" or the status condition, e.g. "PSN" or "FNT".
+    pub status: String,
+    /// The filled part of the HP bar, from 0 to 1.
+    pub hp_bar_fill: f32,
+}
+
+/// Returns the filled part of the HP bar, from 0 to 1.
+///
+/// The bar is framed by a line above and below its filling. The longest
+/// row between the two lines is the filling. Without the frame, like on
+/// some palettes, the longest row is taken.
+///
+/// Expects the image to be a binary image.
+pub fn read_hp_bar(img: &GrayImage, pos: &Position) -> f32 {
+    let rows: Vec<u32> = (pos.y..pos.y + pos.height)
+        .map(|y| {
+            (pos.x..pos.x + pos.width)
+                .filter(|x| img.get_pixel(*x, y)[0] != 0)
+                .count() as u32
+        })
+        .collect();
+
+    let frame: Vec<usize> = (0..rows.len())
+        .filter(|row| rows[*row] == pos.width)
+        .collect();
+    let filling = match frame[..] {
+        [first, .., last] if last - first > 1 => &rows[first + 1..last],
+        _ => &rows[..],
+    };
+
+    let fill = filling.iter().max().copied().unwrap_or(0);
+    fill as f32 / pos.width as f32
+}
+
+/// Returns the position of a HP bar of 6 tiles, given by its first tile.
+fn hp_bar_field(col: u32, row: u32) -> Position {
+    Position {
+        x: col * TILE_SIZE,
+        y: row * TILE_SIZE,
+        width: 6 * TILE_SIZE,
+        height: TILE_SIZE,
+    }
+}
+
+/// Reads the slots of a party menu, up to the first empty one.
+fn read_party_slots(
+    img: &GrayImage,
+    chars: &CharTable,
+    slots: &[PartySlotPositions],
+) -> Result<Vec<PartySlot>, String> {
+    let mut party = Vec::new();
+    for (idx, slot) in slots.iter().enumerate() {
+        let nickname = read_text(img, &slot.nickname, chars, "nickname")?;
+        if nickname.is_empty() {
+            break;
+        }
+
+        let slot_name = |name: &str| format!("{name} of slot {}", idx + 1);
+        let level = read_number(img, &slot.level, chars, &slot_name("level"))?;
+        // Level 100 replaces the ":L" char, leaving "00" in the field
+        let level = if level == 0 { MAX_LEVEL } else { level };
+        let hp = read_number(img, &slot.hp, chars, &slot_name("HP"))?;
+        let max_hp = read_number(img, &slot.max_hp, chars, &slot_name("max HP"))?;
+        let status = read_text(img, &slot.status, chars, &slot_name("status"))?;
+        let status = if status.is_empty() {
+            "OK".to_string()
+        } else {
+            status
+        };
+
+        party.push(PartySlot {
+            nickname,
+            level,
+            hp,
+            max_hp,
+            status,
+            hp_bar_fill: read_hp_bar(img, &slot.hp_bar),
+        });
+    }
+    Ok(party)
+}
+
+/// Returns true if the char at the position is a "/".
+fn is_slash(img: &GrayImage, pos: &Position, chars: &CharTable) -> bool {
+    read_char(img, pos, chars).is_ok_and(|char| char == "/")
+}
+
+/// The layout of the RBY party menu.
+///
+/// Each Pokémon takes two rows: the nickname, level and status on the
+/// first, the HP bar and HP on the second.
+pub struct RbyParty {
+    pub width: i32,
+    pub height: i32,
+    pub slots: [PartySlotPositions; 6],
+}
+
+impl RbyParty {
+    /// Creates a new instance of the RBY party menu layout.
+    pub fn new() -> RbyParty {
+        RbyParty {
+            width: 160,
+            height: 144,
+            slots: [0, 1, 2, 3, 4, 5].map(|idx| {
+                let row = idx * 2;
+                PartySlotPositions {
+                    nickname: tile_field(3, row, 10),
+                    level: tile_field(14, row, 2), // After the ":L" char
+                    status: tile_field(17, row, 3),
+                    hp: tile_field(13, row + 1, 3),
+                    max_hp: tile_field(17, row + 1, 3),
+                    hp_bar: hp_bar_field(6, row + 1),
+                }
+            }),
+        }
+    }
+
+    /// Returns true if the image is the RBY party menu.
+    ///
+    /// Expects the image to be a binary image.
+    pub fn verify_layout(&self, img: &GrayImage, chars: &CharTable) -> bool {
+        if img.width() as i32 != self.width || img.height() as i32 != self.height {
+            return false;
+        }
+
+        // The "/" of the HP of the first Pokémon, drawn a pixel lower in RBY
+        let pos = Position {
+            x: 128,
+            y: 9,
+            width: 7,
+            height: 7,
+        };
+        is_slash(img, &pos, chars)
+    }
+
+    /// Reads the Pokémon of the party from the screen.
+    ///
+    /// Expects the image to be a binary image.
+    pub fn read_fields(
+        &self,
+        img: &GrayImage,
+        chars: &CharTable,
+    ) -> Result<Vec<PartySlot>, String> {
+        if img.width() as i32 != self.width || img.height() as i32 != self.height {
+            return Err("Mismatch in image and layout dimensions.".to_string());
+        }
+        read_party_slots(img, chars, &self.slots)
+    }
+}
+
+impl Default for RbyParty {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The layout of the GSC party menu.
+///
+/// Each Pokémon takes two rows: the nickname and HP on the first, the
+/// status, level and HP bar on the second.
+pub struct GscParty {
+    pub width: i32,
+    pub height: i32,
+    pub slots: [PartySlotPositions; 6],
+}
+
+impl GscParty {
+    /// Creates a new instance of the GSC party menu layout.
+    pub fn new() -> GscParty {
+        GscParty {
+            width: 160,
+            height: 144,
+            slots: [0, 1, 2, 3, 4, 5].map(|idx| {
+                let row = idx * 2 + 1;
+                PartySlotPositions {
+                    nickname: tile_field(3, row, 10),
+                    level: tile_field(9, row + 1, 2), // After the ":L" char
+                    status: tile_field(5, row + 1, 3),
+                    hp: tile_field(13, row, 3),
+                    max_hp: tile_field(17, row, 3),
+                    hp_bar: hp_bar_field(13, row + 1),
+                }
+            }),
+        }
+    }
+
+    /// Returns true if the image is the GSC party menu.
+    ///
+    /// Expects the image to be a binary image.
+    pub fn verify_layout(&self, img: &GrayImage, chars: &CharTable) -> bool {
+        if img.width() as i32 != self.width || img.height() as i32 != self.height {
+            return false;
+        }
+
+        // The "/" of the HP of the first Pokémon
+        let pos = Position {
+            x: 128,
+            y: 8,
+            width: 7,
+            height: 7,
+        };
+        is_slash(img, &pos, chars)
+    }
+
+    /// Reads the Pokémon of the party from the screen.
+    ///
+    /// Expects the image to be a binary image.
+    pub fn read_fields(
+        &self,
+        img: &GrayImage,
+        chars: &CharTable,
+    ) -> Result<Vec<PartySlot>, String> {
+        if img.width() as i32 != self.width || img.height() as i32 != self.height {
+            return Err("Mismatch in image and layout dimensions.".to_string());
+        }
+        read_party_slots(img, chars, &self.slots)
+    }
+}
+
+impl Default for GscParty {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The positions of the fields of a side of the battle screen.
+pub struct BattleSidePositions {
+    pub name: Position,
+    /// The level digits, after the ":L" char.
+    pub level: Position,
+    /// The status condition, shown in place of the level.
+    pub status: Position,
+    /// The gender symbol of GSC, the level moves a tile left without one.
+    pub gender: Option<Position>,
+    pub hp_bar: Position,
+}
+
+impl BattleSidePositions {
+    /// Creates the field positions of a side of the battle screen.
+    ///
+    /// The positions are given in tiles, as (col, row).
+    fn new(
+        name: (u32, u32),
+        level: (u32, u32),
+        status: (u32, u32),
+        gender: Option<(u32, u32)>,
+        hp_bar: (u32, u32),
+    ) -> BattleSidePositions {
+        let field = |(col, row): (u32, u32), len: u32| tile_field(col, row, len);
+        BattleSidePositions {
+            name: field(name, 10),
+            level: field(level, 2),
+            status: field(status, 3),
+            gender: gender.map(|x| field(x, 1)),
+            hp_bar: hp_bar_field(hp_bar.0, hp_bar.1),
+        }
+    }
+}
+
+/// A side of the battle screen, either of the player or of the opponent.
+#[derive(Debug, PartialEq, Clone)]
+pub struct BattleSide {
+    /// The nickname, or the species for the opponent.
+    pub name: String,
+    /// None if the status condition is shown instead.
+    pub level: Option<i32>,
+    /// "OK" or the status condition, e.g. "PSN".
+    pub status: String,
+    pub gender: Option<Gender>,
+    /// The filled pixels of the HP bar, out of [`HP_BAR_PIXELS`].
+    pub hp_bar_pixels: i32,
+}
+
+impl BattleSide {
+    /// Returns the variation of the max HP over the HP DV.
+    ///
+    /// Wild Pokémon and those of trainers have no stat experience.
+    pub fn max_hp(&self, base_hp: i32) -> Result<StatVariation, String> {
+        let level = self
+            .level
+            .ok_or(format!("level of {} not shown", self.name))?;
+        Ok(StatVariation::init(&level, &base_hp, &0, &true))
+    }
+
+    /// Returns the range of HP shown by the HP bar.
+    pub fn hp(&self, base_hp: i32) -> Result<StatRange, String> {
+        hp_from_bar(self.hp_bar_pixels, &self.max_hp(base_hp)?)
+    }
+}
+
+/// Reads a side of the battle screen.
+fn read_battle_side(
+    img: &GrayImage,
+    chars: &CharTable,
+    side: &BattleSidePositions,
+) -> Result<BattleSide, String> {
+    let name = read_text(img, &side.name, chars, "name")?;
+
+    let gender = match &side.gender {
+        Some(pos) => {
+            let gender = read_char(img, pos, chars)
+                .map_err(|err| format!("could not read gender: {err}"))?;
+            Gender::from_symbol(&gender)
+        }
+        None => None,
+    };
+    let mut level_pos = side.level;
+    if side.gender.is_some() && gender.is_none() {
+        level_pos.x -= 8;
+    }
+
+    let level = read_text(img, &level_pos, chars, "level")?;
+    let (level, status) = match level.parse::<i32>() {
+        // Level 100 replaces the ":L" char, leaving "00" in the field
+        Ok(0) => (Some(MAX_LEVEL), "OK".to_string()),
+        Ok(level) => (Some(level), "OK".to_string()),
+        Err(_) => (None, read_text(img, &side.status, chars, "status")?),
+    };
+
+    let fill = read_hp_bar(img, &side.hp_bar);
+    let hp_bar_pixels = (fill * HP_BAR_PIXELS as f32).round() as i32;
+
+    Ok(BattleSide {
+        name,
+        level,
+        status,
+        gender,
+        hp_bar_pixels,
+    })
+}
+
+/// The content of the battle screen.
+#[derive(Debug, PartialEq, Clone)]
+pub struct BattleContent {
+    pub opponent: BattleSide,
+    pub player: BattleSide,
+    /// The HP of the Pokémon of the player, only shown on its side.
+    pub hp: i32,
+    pub max_hp: i32,
+}
+
+/// Reads the fields of a battle screen.
+fn read_battle(
+    img: &GrayImage,
+    chars: &CharTable,
+    opponent: &BattleSidePositions,
+    player: &BattleSidePositions,
+    hp: &Position,
+    max_hp: &Position,
+) -> Result<BattleContent, String> {
+    let opponent =
+        read_battle_side(img, chars, opponent).map_err(|err| format!("opponent side: {err}"))?;
+    let player =
+        read_battle_side(img, chars, player).map_err(|err| format!("player side: {err}"))?;
+    let hp = read_number(img, hp, chars, "HP")?;
+    let max_hp = read_number(img, max_hp, chars, "max HP")?;
+
+    Ok(BattleContent {
+        opponent,
+        player,
+        hp,
+        max_hp,
+    })
+}
+
+/// The layout of the RBY battle screen.
+///
+/// The opponent is shown at the top left, the Pokémon of the player at the
+/// right, above the text box. The names are left-aligned, their fields
+/// cover the longest name.
+pub struct RbyBattle {
+    pub width: i32,
+    pub height: i32,
+    pub opponent: BattleSidePositions,
+    pub player: BattleSidePositions,
+    pub hp: Position,
+    pub max_hp: Position,
+}
+
+impl RbyBattle {
+    /// Creates a new instance of the RBY battle screen layout.
+    pub fn new() -> RbyBattle {
+        RbyBattle {
+            width: 160,
+            height: 144,
+            opponent: BattleSidePositions::new((1, 0), (5, 1), (5, 1), None, (4, 2)),
+            player: BattleSidePositions::new((10, 7), (15, 8), (15, 8), None, (12, 9)),
+            hp: Position {
+                x: 88,
+                y: 80,
+                width: 23,
+                height: 7,
+            },
+            max_hp: Position {
+                x: 120,
+                y: 80,
+                width: 23,
+                height: 7,
+            },
+        }
+    }
+
+    /// Returns true if the image is the RBY battle screen.
+    ///
+    /// Expects the image to be a binary image.
+    pub fn verify_layout(&self, img: &GrayImage, chars: &CharTable) -> bool {
+        if img.width() as i32 != self.width || img.height() as i32 != self.height {
+            return false;
+        }
+
+        // The "/" of the HP of the player, drawn a pixel lower in RBY
+        let pos = Position {
+            x: 112,
+            y: 81,
+            width: 7,
+            height: 7,
+        };
+        is_slash(img, &pos, chars)
+    }
+
+    /// Reads the fields of the layout from the screen.
+    ///
+    /// Expects the image to be a binary image.
+    pub fn read_fields(&self, img: &GrayImage, chars: &CharTable) -> Result<BattleContent, String> {
+        if img.width() as i32 != self.width || img.height() as i32 != self.height {
+            return Err("Mismatch in image and layout dimensions.".to_string());
+        }
+        read_battle(
+            img,
+            chars,
+            &self.opponent,
+            &self.player,
+            &self.hp,
+            &self.max_hp,
+        )
+    }
+}
+
+impl Default for RbyBattle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The layout of the GSC battle screen.
+///
+/// Like in RBY, except for the gender symbols after the levels.
+pub struct GscBattle {
+    pub width: i32,
+    pub height: i32,
+    pub opponent: BattleSidePositions,
+    pub player: BattleSidePositions,
+    pub hp: Position,
+    pub max_hp: Position,
+}
+
+impl GscBattle {
+    /// Creates a new instance of the GSC battle screen layout.
+    pub fn new() -> GscBattle {
+        GscBattle {
+            width: 160,
+            height: 144,
+            opponent: BattleSidePositions::new((1, 0), (7, 1), (6, 1), Some((9, 1)), (4, 2)),
+            player: BattleSidePositions::new((10, 7), (15, 8), (14, 8), Some((17, 8)), (12, 9)),
+            hp: Position {
+                x: 88,
+                y: 80,
+                width: 23,
+                height: 7,
+            },
+            max_hp: Position {
+                x: 120,
+                y: 80,
+                width: 23,
+                height: 7,
+            },
+        }
+    }
+
+    /// Returns true if the image is the GSC battle screen.
+    ///
+    /// Expects the image to be a binary image.
+    pub fn verify_layout(&self, img: &GrayImage, chars: &CharTable) -> bool {
+        if img.width() as i32 != self.width || img.height() as i32 != self.height {
+            return false;
+        }
+
+        // The "/" of the HP of the player
+        let pos = Position {
+            x: 112,
+            y: 80,
+            width: 7,
+            height: 7,
+        };
+        is_slash(img, &pos, chars)
+    }
+
+    /// Reads the fields of the layout from the screen.
+    ///
+    /// Expects the image to be a binary image.
+    pub fn read_fields(&self, img: &GrayImage, chars: &CharTable) -> Result<BattleContent, String> {
+        if img.width() as i32 != self.width || img.height() as i32 != self.height {
+            return Err("Mismatch in image and layout dimensions.".to_string());
+        }
+        read_battle(
+            img,
+            chars,
+            &self.opponent,
+            &self.player,
+            &self.hp,
+            &self.max_hp,
+        )
+    }
+}
+
+impl Default for GscBattle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The positions of the fields of the FIGHT menu.
+///
+/// The menu is the same in RBY and GSC. The moves are listed at the bottom
+/// right, the box above them describes the move under the cursor.
+pub struct FightMenuPositions {
+    /// The cursors in front of the moves.
+    pub cursors: [Position; 4],
+    pub moves: [Position; 4],
+    /// The "TYPE" label of the move box.
+    pub type_label: Position,
+    pub type_: Position,
+    pub pp: Position,
+    pub max_pp: Position,
+}
+
+impl FightMenuPositions {
+    /// Creates the field positions of the FIGHT menu.
+    pub fn new() -> FightMenuPositions {
+        FightMenuPositions {
+            cursors: [13, 14, 15, 16].map(|row| tile_field(5, row, 1)),
+            moves: [13, 14, 15, 16].map(|row| tile_field(6, row, 12)),
+            type_label: tile_field(1, 9, 4),
+            type_: tile_field(2, 10, 8),
+            pp: tile_field(5, 11, 2),
+            max_pp: tile_field(8, 11, 2),
+        }
+    }
+}
+
+impl Default for FightMenuPositions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The content of the FIGHT menu.
+#[derive(Debug, PartialEq, Clone)]
+pub struct FightMenuContent {
+    /// The index of the move under the cursor.
+    pub cursor: usize,
+    /// The names of the moves, "-" for the empty slots.
+    pub moves: [String; 4],
+    /// The type of the move under the cursor.
+    pub type_: String,
+    /// The remaining PP of the move under the cursor.
+    pub pp: i32,
+    pub max_pp: i32,
+}
+
+impl FightMenuContent {
+    /// Returns the name of the move under the cursor.
+    pub fn selected_move(&self) -> &str {
+        &self.moves[self.cursor]
+    }
+}
+
+/// Returns true if the FIGHT menu is shown, with the "/" of the PP at the position.
+fn is_fight_menu(
+    img: &GrayImage,
+    chars: &CharTable,
+    menu: &FightMenuPositions,
+    slash: &Position,
+) -> bool {
+    let label = read_field(img, &menu.type_label, chars);
+    label.is_ok_and(|x| x == "TYPE") && is_slash(img, slash, chars)
+}
+
+/// Returns the index of the first of the positions with the cursor.
+///
+/// The cursor is not among the known characters, any pixel at a position
+/// is taken as the cursor.
+fn find_cursor(img: &GrayImage, cursors: &[Position]) -> Result<usize, String> {
+    for (idx, pos) in cursors.iter().enumerate() {
+        if Roi::new(img, *pos)?.iter().any(|x| *x != 0) {
+            return Ok(idx);
+        }
+    }
+    Err("could not find the cursor".to_string())
+}
+
+/// Reads the fields of the FIGHT menu.
+fn read_fight_menu(
+    img: &GrayImage,
+    chars: &CharTable,
+    menu: &FightMenuPositions,
+) -> Result<FightMenuContent, String> {
+    let cursor = find_cursor(img, &menu.cursors)?;
+
+    let mut moves: [String; 4] = Default::default();
+    for (idx, pos) in menu.moves.iter().enumerate() {
+        moves[idx] = read_text(img, pos, chars, &format!("move {}", idx + 1))?;
+    }
+
+    Ok(FightMenuContent {
+        cursor,
+        moves,
+        type_: read_text(img, &menu.type_, chars, "type")?,
+        pp: read_number(img, &menu.pp, chars, "PP")?,
+        max_pp: read_number(img, &menu.max_pp, chars, "max PP")?,
+    })
+}
+
+/// The layout of the RBY FIGHT menu.
+pub struct RbyFightMenu {
+    pub width: i32,
+    pub height: i32,
+    pub menu: FightMenuPositions,
+}
+
+impl RbyFightMenu {
+    /// Creates a new instance of the RBY FIGHT menu layout.
+    pub fn new() -> RbyFightMenu {
+        RbyFightMenu {
+            width: 160,
+            height: 144,
+            menu: FightMenuPositions::new(),
+        }
+    }
+
+    /// Returns true if the image is the RBY FIGHT menu.
+    ///
+    /// Expects the image to be a binary image.
+    pub fn verify_layout(&self, img: &GrayImage, chars: &CharTable) -> bool {
+        if img.width() as i32 != self.width || img.height() as i32 != self.height {
+            return false;
+        }
+
+        // The "/" of the PP, drawn a pixel lower in RBY
+        let pos = Position {
+            x: 56,
+            y: 89,
+            width: 7,
+            height: 7,
+        };
+        is_fight_menu(img, chars, &self.menu, &pos)
+    }
+
+    /// Reads the fields of the layout from the screen.
+    ///
+    /// Expects the image to be a binary image.
+    pub fn read_fields(
+        &self,
+        img: &GrayImage,
+        chars: &CharTable,
+    ) -> Result<FightMenuContent, String> {
+        if img.width() as i32 != self.width || img.height() as i32 != self.height {
+            return Err("Mismatch in image and layout dimensions.".to_string());
+        }
+        read_fight_menu(img, chars, &self.menu)
+    }
+}
+
+impl Default for RbyFightMenu {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The layout of the GSC FIGHT menu.
+pub struct GscFightMenu {
+    pub width: i32,
+    pub height: i32,
+    pub menu: FightMenuPositions,
+}
+
+impl GscFightMenu {
+    /// Creates a new instance of the GSC FIGHT menu layout.
+    pub fn new() -> GscFightMenu {
+        GscFightMenu {
+            width: 160,
+            height: 144,
+            menu: FightMenuPositions::new(),
+        }
+    }
+
+    /// Returns true if the image is the GSC FIGHT menu.
+    ///
+    /// Expects the image to be a binary image.
+    pub fn verify_layout(&self, img: &GrayImage, chars: &CharTable) -> bool {
+        if img.width() as i32 != self.width || img.height() as i32 != self.height {
+            return false;
+        }
+
+        // The "/" of the PP
+        let pos = Position {
+            x: 56,
+            y: 88,
+            width: 7,
+            height: 7,
+        };
+        is_fight_menu(img, chars, &self.menu, &pos)
+    }
+
+    /// Reads the fields of the layout from the screen.
+    ///
+    /// Expects the image to be a binary image.
+    pub fn read_fields(
+        &self,
+        img: &GrayImage,
+        chars: &CharTable,
+    ) -> Result<FightMenuContent, String> {
+        if img.width() as i32 != self.width || img.height() as i32 != self.height {
+            return Err("Mismatch in image and layout dimensions.".to_string());
+        }
+        read_fight_menu(img, chars, &self.menu)
+    }
+}
+
+impl Default for GscFightMenu {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Reads the name and the new level from the level-up message.
+fn read_level_up_message(
+    img: &GrayImage,
+    chars: &CharTable,
+    dialog: &DialogBox,
+) -> Result<(String, i32), String> {
+    let tilemap = TileMap::read(img, chars)?;
+    let text = dialog.read_text(&tilemap);
+    match DialogEvent::parse(&text) {
+        Some(DialogEvent::LevelUp { name, level }) => Ok((name, level)),
+        _ => Err(format!("could not find the level-up message in '{text}'")),
+    }
+}
+
+/// The layout of the RBY level-up stats box.
+///
+/// After a level up, the stats other than HP are shown in a box at the top
+/// right, while the text box still tells the new level.
+pub struct RbyLevelUp {
+    pub width: i32,
+    pub height: i32,
+    /// The "ATTACK" label of the box.
+    pub label: Position,
+    pub attack: Position,
+    pub defense: Position,
+    pub speed: Position,
+    pub special: Position,
+    pub dialog: DialogBox,
+}
+
+impl RbyLevelUp {
+    /// Creates a new instance of the RBY level-up stats box layout.
+    pub fn new() -> RbyLevelUp {
+        RbyLevelUp {
+            width: 160,
+            height: 144,
+            label: tile_field(11, 3, 6),
+            attack: tile_field(15, 4, 3),
+            defense: tile_field(15, 6, 3),
+            speed: tile_field(15, 8, 3),
+            special: tile_field(15, 10, 3),
+            dialog: DialogBox::new(),
+        }
+    }
+
+    /// Returns true if the image is the RBY level-up stats box.
+    ///
+    /// Expects the image to be a binary image.
+    pub fn verify_layout(&self, img: &GrayImage, chars: &CharTable) -> bool {
+        if img.width() as i32 != self.width || img.height() as i32 != self.height {
+            return false;
+        }
+        read_field(img, &self.label, chars).is_ok_and(|x| x == "ATTACK")
+    }
+
+    /// Reads the fields of the layout from the screen.
+    ///
+    /// Expects the image to be a binary image.
+    pub fn read_fields(
+        &self,
+        img: &GrayImage,
+        chars: &CharTable,
+    ) -> Result<RbyLevelUpContent, String> {
+        if img.width() as i32 != self.width || img.height() as i32 != self.height {
+            return Err("Mismatch in image and layout dimensions.".to_string());
+        }
+
+        let (name, level) = read_level_up_message(img, chars, &self.dialog)?;
+        Ok(RbyLevelUpContent {
+            name,
+            level,
+            attack: read_number(img, &self.attack, chars, "attack")?,
+            defense: read_number(img, &self.defense, chars, "defense")?,
+            speed: read_number(img, &self.speed, chars, "speed")?,
+            special: read_number(img, &self.special, chars, "special")?,
+        })
+    }
+}
+
+impl Default for RbyLevelUp {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The content of the RBY level-up stats box.
+#[derive(Debug, PartialEq, Clone)]
+pub struct RbyLevelUpContent {
+    /// The nickname told by the level-up message.
+    pub name: String,
+    pub level: i32,
+    pub attack: i32,
+    pub defense: i32,
+    pub speed: i32,
+    pub special: i32,
+}
+
+/// The layout of the GSC level-up stats box.
+///
+/// Like in RBY, but the box starts at the top of the screen and shows both
+/// special stats.
+pub struct GscLevelUp {
+    pub width: i32,
+    pub height: i32,
+    /// The "ATTACK" label of the box.
+    pub label: Position,
+    pub attack: Position,
+    pub defense: Position,
+    pub spc_attack: Position,
+    pub spc_defense: Position,
+    pub speed: Position,
+    pub dialog: DialogBox,
+}
+
+impl GscLevelUp {
+    /// Creates a new instance of the GSC level-up stats box layout.
+    pub fn new() -> GscLevelUp {
+        GscLevelUp {
+            width: 160,
+            height: 144,
+            label: tile_field(11, 1, 6),
+            attack: tile_field(15, 2, 3),
+            defense: tile_field(15, 4, 3),
+            spc_attack: tile_field(15, 6, 3),
+            spc_defense: tile_field(15, 8, 3),
+            speed: tile_field(15, 10, 3),
+            dialog: DialogBox::new(),
+        }
+    }
+
+    /// Returns true if the image is the GSC level-up stats box.
+    ///
+    /// Expects the image to be a binary image.
+    pub fn verify_layout(&self, img: &GrayImage, chars: &CharTable) -> bool {
+        if img.width() as i32 != self.width || img.height() as i32 != self.height {
+            return false;
+        }
+        read_field(img, &self.label, chars).is_ok_and(|x| x == "ATTACK")
+    }
+
+    /// Reads the fields of the layout from the screen.
+    ///
+    /// Expects the image to be a binary image.
+    pub fn read_fields(
+        &self,
+        img: &GrayImage,
+        chars: &CharTable,
+    ) -> Result<GscLevelUpContent, String> {
+        if img.width() as i32 != self.width || img.height() as i32 != self.height {
+            return Err("Mismatch in image and layout dimensions.".to_string());
+        }
+
+        let (name, level) = read_level_up_message(img, chars, &self.dialog)?;
+        Ok(GscLevelUpContent {
+            name,
+            level,
+            attack: read_number(img, &self.attack, chars, "attack")?,
+            defense: read_number(img, &self.defense, chars, "defense")?,
+            spc_attack: read_number(img, &self.spc_attack, chars, "special attack")?,
+            spc_defense: read_number(img, &self.spc_defense, chars, "special defense")?,
+            speed: read_number(img, &self.speed, chars, "speed")?,
+        })
+    }
+}
+
+impl Default for GscLevelUp {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The content of the GSC level-up stats box.
+#[derive(Debug, PartialEq, Clone)]
+pub struct GscLevelUpContent {
+    /// The nickname told by the level-up message.
+    pub name: String,
+    pub level: i32,
+    pub attack: i32,
+    pub defense: i32,
+    pub spc_attack: i32,
+    pub spc_defense: i32,
+    pub speed: i32,
+}
+
+/// The positions of the fields of a Pokédex entry.
+pub struct PokedexEntryPositions {
+    pub name: Position,
+    pub category: Position,
+    pub ndex: Position,
+    /// The "HT" label of the height.
+    pub height_label: Position,
+    pub feet: Position,
+    pub inches: Position,
+    /// The weight, with its decimal point.
+    pub weight: Position,
+    /// The tile column of the description.
+    pub text_col: u32,
+    /// The tile rows of the lines of the description.
+    pub text_rows: [u32; 3],
+    /// The number of characters per line of the description.
+    pub text_len: u32,
+}
+
+impl PokedexEntryPositions {
+    /// Creates the field positions of a Pokédex entry.
+    ///
+    /// The rows of the name, the category and the height are given, the
+    /// weight is shown two rows below the height.
+    fn new(
+        name_row: u32,
+        category_row: u32,
+        height_row: u32,
+        text_col: u32,
+    ) -> PokedexEntryPositions {
+        PokedexEntryPositions {
+            name: tile_field(9, name_row, 10),
+            category: tile_field(9, category_row, 10),
+            ndex: tile_field(4, 8, 3), // After the "No." chars
+            height_label: tile_field(9, height_row, 2),
+            feet: tile_field(12, height_row, 2),
+            inches: tile_field(15, height_row, 2),
+            weight: tile_field(11, height_row + 2, 6),
+            text_col,
+            text_rows: [11, 13, 15],
+            text_len: 19 - text_col,
+        }
+    }
+}
+
+/// The content of a Pokédex entry.
+#[derive(Debug, PartialEq, Clone)]
+pub struct PokedexEntryContent {
+    pub ndex: i32,
+    pub name: String,
+    /// The kind of Pokémon, e.g. "MOUSE".
+    pub category: String,
+    /// The height in feet and inches, None if the Pokémon was not caught.
+    pub height: Option<(i32, i32)>,
+    /// The weight in pounds, None if the Pokémon was not caught.
+    pub weight: Option<f32>,
+    /// The first page of the description, empty if the Pokémon was not caught.
+    pub description: String,
+}
+
+/// Reads the fields of a Pokédex entry.
+///
+/// The height and weight of the Pokémon not caught are question marks.
+fn read_pokedex_entry(
+    img: &GrayImage,
+    chars: &CharTable,
+    entry: &PokedexEntryPositions,
+) -> Result<PokedexEntryContent, String> {
+    let ndex = read_number(img, &entry.ndex, chars, "ndex")?;
+    let name = read_text(img, &entry.name, chars, "name")?;
+    let category = read_text(img, &entry.category, chars, "category")?;
+
+    let feet = read_number(img, &entry.feet, chars, "feet");
+    let inches = read_number(img, &entry.inches, chars, "inches");
+    let height = feet.and_then(|feet| Ok((feet, inches?))).ok();
+    let weight = read_text(img, &entry.weight, chars, "weight")
+        .ok()
+        .and_then(|x| x.parse::<f32>().ok());
+
+    // The tile map is used for the descenders of the mixed case text
+    let tilemap = TileMap::read(img, chars)?;
+    let lines: Vec<String> = entry
+        .text_rows
+        .iter()
+        .map(|row| {
+            tilemap
+                .text(entry.text_col, *row, entry.text_len)
+                .trim()
+                .to_string()
+        })
+        .filter(|line| !line.is_empty())
+        .collect();
+
+    Ok(PokedexEntryContent {
+        ndex,
+        name,
+        category,
+        height,
+        weight,
+        description: lines.join(" "),
+    })
+}
+
+/// The layout of the RBY Pokédex entry.
+pub struct RbyPokedexEntry {
+    pub width: i32,
+    pub height: i32,
+    pub entry: PokedexEntryPositions,
+}
+
+impl RbyPokedexEntry {
+    /// Creates a new instance of the RBY Pokédex entry layout.
+    pub fn new() -> RbyPokedexEntry {
+        RbyPokedexEntry {
+            width: 160,
+            height: 144,
+            entry: PokedexEntryPositions::new(2, 4, 6, 1),
+        }
+    }
+
+    /// Returns true if the image is the RBY Pokédex entry.
+    ///
+    /// Expects the image to be a binary image.
+    pub fn verify_layout(&self, img: &GrayImage, chars: &CharTable) -> bool {
+        if img.width() as i32 != self.width || img.height() as i32 != self.height {
+            return false;
+        }
+        read_field(img, &self.entry.height_label, chars).is_ok_and(|x| x == "HT")
+    }
+
+    /// Reads the fields of the layout from the screen.
+    ///
+    /// Expects the image to be a binary image.
+    pub fn read_fields(
+        &self,
+        img: &GrayImage,
+        chars: &CharTable,
+    ) -> Result<PokedexEntryContent, String> {
+        if img.width() as i32 != self.width || img.height() as i32 != self.height {
+            return Err("Mismatch in image and layout dimensions.".to_string());
+        }
+        read_pokedex_entry(img, chars, &self.entry)
+    }
+}
+
+impl Default for RbyPokedexEntry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The layout of the GSC Pokédex entry.
+///
+/// Like in RBY, but the fields right of the picture start a row lower.
+pub struct GscPokedexEntry {
+    pub width: i32,
+    pub height: i32,
+    pub entry: PokedexEntryPositions,
+}
+
+impl GscPokedexEntry {
+    /// Creates a new instance of the GSC Pokédex entry layout.
+    pub fn new() -> GscPokedexEntry {
+        GscPokedexEntry {
+            width: 160,
+            height: 144,
+            entry: PokedexEntryPositions::new(3, 5, 7, 2),
+        }
+    }
+
+    /// Returns true if the image is the GSC Pokédex entry.
+    ///
+    /// Expects the image to be a binary image.
+    pub fn verify_layout(&self, img: &GrayImage, chars: &CharTable) -> bool {
+        if img.width() as i32 != self.width || img.height() as i32 != self.height {
+            return false;
+        }
+        read_field(img, &self.entry.height_label, chars).is_ok_and(|x| x == "HT")
+    }
+
+    /// Reads the fields of the layout from the screen.
+    ///
+    /// Expects the image to be a binary image.
+    pub fn read_fields(
+        &self,
+        img: &GrayImage,
+        chars: &CharTable,
+    ) -> Result<PokedexEntryContent, String> {
+        if img.width() as i32 != self.width || img.height() as i32 != self.height {
+            return Err("Mismatch in image and layout dimensions.".to_string());
+        }
+        read_pokedex_entry(img, chars, &self.entry)
+    }
+}
+
+impl Default for GscPokedexEntry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A Pokémon listed in a box of Bill's PC.
+#[derive(Debug, PartialEq, Clone, serde::Serialize)]
+pub struct BoxEntry {
+    pub name: String,
+    /// The level, None if not shown.
+    pub level: Option<i32>,
+    /// The species, None if not shown.
+    pub species: Option<String>,
+}
+
+/// The visible part of the list of a box of Bill's PC.
+#[derive(Debug, PartialEq, Clone)]
+pub struct BoxListContent {
+    /// The entries from the top of the list on the screen.
+    pub entries: Vec<BoxEntry>,
+    /// The index of the entry under the cursor, the number of entries for
+    /// the "CANCEL" at the end.
+    pub cursor: usize,
+    /// True if the "CANCEL" at the end of the list is shown.
+    pub is_end: bool,
+}
+
+/// Reads the names of a list menu, up to the "CANCEL" or the first blank.
+///
+/// Returns the names and if the "CANCEL" was found.
+fn read_list_names(
+    img: &GrayImage,
+    chars: &CharTable,
+    names: &[Position],
+) -> Result<(Vec<String>, bool), String> {
+    let mut entries = Vec::new();
+    for (idx, pos) in names.iter().enumerate() {
+        let name = read_text(img, pos, chars, &format!("name of entry {}", idx + 1))?;
+        match name.as_str() {
+            "CANCEL" => return Ok((entries, true)),
+            "" => break,
+            _ => entries.push(name),
+        }
+    }
+    Ok((entries, false))
+}
+
+/// Reads a level after the ":L" char.
+fn read_level(img: &GrayImage, pos: &Position, chars: &CharTable) -> Result<i32, String> {
+    let level = read_number(img, pos, chars, "level")?;
+    // Level 100 replaces the ":L" char, leaving "00" in the field
+    Ok(if level == 0 { MAX_LEVEL } else { level })
+}
+
+/// The layout of the RBY box list of Bill's PC.
+///
+/// The list of the "WITHDRAW" and "RELEASE" options shows four Pokémon,
+/// each with its level on the row below the name.
+pub struct RbyBoxList {
+    pub width: i32,
+    pub height: i32,
+    /// The cursors in front of the entries.
+    pub cursors: [Position; 4],
+    pub names: [Position; 4],
+    /// The level digits, after the ":L" char.
+    pub levels: [Position; 4],
+}
+
+impl RbyBoxList {
+    /// Creates a new instance of the RBY box list layout.
+    pub fn new() -> RbyBoxList {
+        let rows = [4, 6, 8, 10];
+        RbyBoxList {
+            width: 160,
+            height: 144,
+            cursors: rows.map(|row| tile_field(5, row, 1)),
+            names: rows.map(|row| tile_field(6, row, 10)),
+            levels: rows.map(|row| tile_field(12, row + 1, 2)),
+        }
+    }
+
+    /// Returns true if the image is the RBY box list.
+    ///
+    /// Expects the image to be a binary image.
+    pub fn verify_layout(&self, img: &GrayImage, chars: &CharTable) -> bool {
+        if img.width() as i32 != self.width || img.height() as i32 != self.height {
+            return false;
+        }
+        // Either a Pokémon with its level or the "CANCEL" of an empty box
+        match read_field(img, &self.names[0], chars) {
+            Ok(name) if name.trim() == "CANCEL" => true,
+            Ok(name) if !name.trim().is_empty() => read_level(img, &self.levels[0], chars).is_ok(),
+            _ => false,
+        }
+    }
+
+    /// Reads the visible entries of the list from the screen.
+    ///
+    /// Expects the image to be a binary image.
+    pub fn read_fields(
+        &self,
+        img: &GrayImage,
+        chars: &CharTable,
+    ) -> Result<BoxListContent, String> {
+        if img.width() as i32 != self.width || img.height() as i32 != self.height {
+            return Err("Mismatch in image and layout dimensions.".to_string());
+        }
+
+        let (names, is_end) = read_list_names(img, chars, &self.names)?;
+        let mut entries = Vec::new();
+        for (name, pos) in names.into_iter().zip(&self.levels) {
+            let level = read_level(img, pos, chars)
+                .map_err(|err| format!("could not read level of '{name}': {err}"))?;
+            entries.push(BoxEntry {
+                name,
+                level: Some(level),
+                species: None,
+            });
+        }
+
+        Ok(BoxListContent {
+            entries,
+            cursor: find_cursor(img, &self.cursors)?,
+            is_end,
+        })
+    }
+}
+
+impl Default for RbyBoxList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The layout of the GSC box list of Bill's PC.
+///
+/// The list on the right shows the names of five Pokémon. The species and
+/// level of the Pokémon under the cursor are shown on the left, below its
+/// picture.
+pub struct GscBoxList {
+    pub width: i32,
+    pub height: i32,
+    /// The cursors in front of the entries.
+    pub cursors: [Position; 5],
+    pub names: [Position; 5],
+    /// The species of the Pokémon under the cursor.
+    pub species: Position,
+    /// The level digits of the Pokémon under the cursor, after the ":L" char.
+    pub level: Position,
+}
+
+impl GscBoxList {
+    /// Creates a new instance of the GSC box list layout.
+    pub fn new() -> GscBoxList {
+        let rows = [4, 6, 8, 10, 12];
+        GscBoxList {
+            width: 160,
+            height: 144,
+            cursors: rows.map(|row| tile_field(8, row, 1)),
+            names: rows.map(|row| tile_field(9, row, 10)),
+            species: tile_field(1, 14, 10),
+            level: tile_field(1, 12, 2),
+        }
+    }
+
+    /// Returns true if the image is the GSC box list.
+    ///
+    /// Expects the image to be a binary image.
+    pub fn verify_layout(&self, img: &GrayImage, chars: &CharTable) -> bool {
+        if img.width() as i32 != self.width || img.height() as i32 != self.height {
+            return false;
+        }
+        // Either a Pokémon with its details or the "CANCEL" of an empty box
+        match read_field(img, &self.names[0], chars) {
+            Ok(name) if name.trim() == "CANCEL" => true,
+            Ok(name) if !name.trim().is_empty() => read_level(img, &self.level, chars).is_ok(),
+            _ => false,
+        }
+    }
+
+    /// Reads the visible entries of the list from the screen.
+    ///
+    /// Only the entry under the cursor has a species and level.
+    ///
+    /// Expects the image to be a binary image.
+    pub fn read_fields(
+        &self,
+        img: &GrayImage,
+        chars: &CharTable,
+    ) -> Result<BoxListContent, String> {
+        if img.width() as i32 != self.width || img.height() as i32 != self.height {
+            return Err("Mismatch in image and layout dimensions.".to_string());
+        }
+
+        let (names, is_end) = read_list_names(img, chars, &self.names)?;
+        let cursor = find_cursor(img, &self.cursors)?;
+        let mut entries: Vec<BoxEntry> = names
+            .into_iter()
+            .map(|name| BoxEntry {
+                name,
+                level: None,
+                species: None,
+            })
+            .collect();
+
+        if let Some(entry) = entries.get_mut(cursor) {
+            entry.level = Some(read_level(img, &self.level, chars)?);
+            entry.species = Some(read_text(img, &self.species, chars, "species")?);
+        }
+
+        Ok(BoxListContent {
+            entries,
+            cursor,
+            is_end,
+        })
+    }
+}
+
+impl Default for GscBoxList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The badges of RBY, in the order they are shown on the trainer card.
+pub const RBY_BADGES: [&str; 8] = [
+    "Boulder", "Cascade", "Thunder", "Rainbow", "Soul", "Marsh", "Volcano", "Earth",
+];
+
+/// The Johto badges of GSC, shown on the second page of the trainer card.
+pub const JOHTO_BADGES: [&str; 8] = [
+    "Zephyr", "Hive", "Plain", "Fog", "Storm", "Mineral", "Glacier", "Rising",
+];
+
+/// The Kanto badges of GSC, shown on the third page of the trainer card.
+pub const KANTO_BADGES: [&str; 8] = RBY_BADGES;
+
+/// Parses the badges of a set, separated by commas, e.g. "Boulder, Soul".
+///
+/// The case is ignored. Needed for RBY, whose trainer card can not be read
+/// for the badges, see [`RbyTrainerCard`].
+pub fn parse_badges(text: &str, set: &[&'static str]) -> Result<Vec<&'static str>, String> {
+    text.split(',')
+        .map(|name| name.trim())
+        .filter(|name| !name.is_empty())
+        .map(|name| {
+            set.iter()
+                .find(|badge| badge.eq_ignore_ascii_case(name))
+                .copied()
+                .ok_or(format!("unknown badge '{name}'"))
+        })
+        .collect()
+}
+
+/// The content of the trainer card.
+#[derive(Debug, PartialEq, Clone)]
+pub struct TrainerCardContent {
+    pub name: String,
+    /// The ID of the player, None in RBY as the card does not show it.
+    pub id: Option<i32>,
+    pub money: i32,
+    /// The play time in hours and minutes.
+    pub play_time: (i32, i32),
+}
+
+impl TrainerCardContent {
+    /// Returns true if a Pokémon with the OT and ID was traded to the player.
+    ///
+    /// Traded Pokémon gain boosted EXP. The IDs are compared if both are
+    /// known, otherwise only the OT is.
+    pub fn is_traded(&self, ot: &str, id: Option<i32>) -> bool {
+        self.name != ot || self.id.zip(id).is_some_and(|(x, y)| x != y)
+    }
+}
+
+/// The pages of the GSC trainer card, turned with the A button.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum GscCardPage {
+    /// The first page, with the ID, money and play time.
+    First,
+    /// The second page, with the Johto badges.
+    Johto,
+    /// The third page, with the Kanto badges.
+    Kanto,
+}
+
+impl GscCardPage {
+    /// Returns the badges shown on the page, none on the first page.
+    pub fn badges(&self) -> &'static [&'static str] {
+        match self {
+            GscCardPage::First => &[],
+            GscCardPage::Johto => &JOHTO_BADGES,
+            GscCardPage::Kanto => &KANTO_BADGES,
+        }
+    }
+}
+
+/// Reads the play time, shown as hours and minutes, like "12:05".
+fn read_play_time(
+    img: &GrayImage,
+    pos: &Position,
+    chars: &CharTable,
+) -> Result<(i32, i32), String> {
+    let text = read_text(img, pos, chars, "play time")?;
+    text.split_once(':')
+        .and_then(|(hours, minutes)| Some((hours.parse().ok()?, minutes.parse().ok()?)))
+        .ok_or(format!("could not parse play time '{text}'"))
+}
+
+/// Returns the badges whose sprite is shown in their slot.
+///
+/// A slot is blank until its badge is obtained. The badge sprites are not
+/// among the known characters, a slot with more than an eighth of its
+/// pixels set is taken as a badge.
+fn read_badges(
+    img: &GrayImage,
+    slots: &[Position],
+    names: &[&'static str],
+) -> Result<Vec<&'static str>, String> {
+    let mut badges = Vec::new();
+    for (pos, name) in slots.iter().zip(names) {
+        let count = Roi::new(img, *pos)?.iter().filter(|x| **x != 0).count() as u32;
+        if count * 8 > pos.width * pos.height {
+            badges.push(*name);
+        }
+    }
+    Ok(badges)
+}
+
+/// The layout of the RBY trainer card.
+///
+/// The card does not show the ID. The badges are not read: they are drawn
+/// over the faces of the gym leaders, and without the sprites of both a
+/// face can not be told from a badge.
+pub struct RbyTrainerCard {
+    pub width: i32,
+    pub height: i32,
+    pub name: Position,
+    /// The "MONEY" of the "MONEY/" label.
+    pub money_label: Position,
+    /// The digits of the money, after the "¥" char.
+    pub money: Position,
+    pub play_time: Position,
+}
+
+impl RbyTrainerCard {
+    /// Creates a new instance of the RBY trainer card layout.
+    pub fn new() -> RbyTrainerCard {
+        RbyTrainerCard {
+            width: 160,
+            height: 144,
+            name: tile_field(7, 2, 7),
+            money_label: tile_field(2, 4, 5),
+            money: tile_field(9, 4, 6),
+            play_time: tile_field(9, 6, 6),
+        }
+    }
+
+    /// Returns true if the image is the RBY trainer card.
+    ///
+    /// Expects the image to be a binary image.
+    pub fn verify_layout(&self, img: &GrayImage, chars: &CharTable) -> bool {
+        if img.width() as i32 != self.width || img.height() as i32 != self.height {
+            return false;
+        }
+        read_field(img, &self.money_label, chars).is_ok_and(|x| x == "MONEY")
+    }
+
+    /// Reads the fields of the layout from the screen.
+    ///
+    /// Expects the image to be a binary image.
+    pub fn read_fields(
+        &self,
+        img: &GrayImage,
+        chars: &CharTable,
+    ) -> Result<TrainerCardContent, String> {
+        if img.width() as i32 != self.width || img.height() as i32 != self.height {
+            return Err("Mismatch in image and layout dimensions.".to_string());
+        }
+
+        Ok(TrainerCardContent {
+            name: read_text(img, &self.name, chars, "name")?,
+            id: None,
+            money: read_number(img, &self.money, chars, "money")?,
+            play_time: read_play_time(img, &self.play_time, chars)?,
+        })
+    }
+}
+
+impl Default for RbyTrainerCard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The layout of the first page of the GSC trainer card.
+///
+/// The badges are shown on the next pages, see [`GscBadgePage`].
+pub struct GscTrainerCard {
+    pub width: i32,
+    pub height: i32,
+    pub name: Position,
+    /// The digits of the ID, after the "ID No." chars.
+    pub id: Position,
+    pub money_label: Position,
+    /// The digits of the money, after the "¥" char.
+    pub money: Position,
+    pub play_time: Position,
+}
+
+impl GscTrainerCard {
+    /// Creates a new instance of the GSC trainer card layout.
+    pub fn new() -> GscTrainerCard {
+        GscTrainerCard {
+            width: 160,
+            height: 144,
+            name: tile_field(7, 2, 7),
+            id: tile_field(5, 4, 5),
+            money_label: tile_field(2, 6, 5),
+            money: tile_field(8, 6, 6),
+            play_time: tile_field(12, 12, 6),
+        }
+    }
+
+    /// Returns true if the image is the first page of the GSC trainer card.
+    ///
+    /// Expects the image to be a binary image.
+    pub fn verify_layout(&self, img: &GrayImage, chars: &CharTable) -> bool {
+        if img.width() as i32 != self.width || img.height() as i32 != self.height {
+            return false;
+        }
+        read_field(img, &self.money_label, chars).is_ok_and(|x| x == "MONEY")
+    }
+
+    /// Reads the fields of the layout from the screen.
+    ///
+    /// Expects the image to be a binary image.
+    pub fn read_fields(
+        &self,
+        img: &GrayImage,
+        chars: &CharTable,
+    ) -> Result<TrainerCardContent, String> {
+        if img.width() as i32 != self.width || img.height() as i32 != self.height {
+            return Err("Mismatch in image and layout dimensions.".to_string());
+        }
+
+        Ok(TrainerCardContent {
+            name: read_text(img, &self.name, chars, "name")?,
+            id: Some(read_number(img, &self.id, chars, "ID")?),
+            money: read_number(img, &self.money, chars, "money")?,
+            play_time: read_play_time(img, &self.play_time, chars)?,
+        })
+    }
+}
+
+impl Default for GscTrainerCard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The layout of the badge pages of the GSC trainer card.
+///
+/// The second page shows the Johto badges, the third the Kanto badges, both
+/// in two rows of four next to the faces of the leaders. The pages look
+/// alike, see [`GscCardPage`] for telling them apart.
+pub struct GscBadgePage {
+    pub width: i32,
+    pub height: i32,
+    /// The area of the faces and the badges, differing between the pages.
+    pub leaders: Position,
+    pub badges: [Position; 8],
+}
+
+impl GscBadgePage {
+    /// Creates a new instance of the GSC badge page layout.
+    pub fn new() -> GscBadgePage {
+        GscBadgePage {
+            width: 160,
+            height: 144,
+            leaders: Position {
+                x: 0,
+                y: 64,
+                width: 160,
+                height: 80,
+            },
+            badges: [
+                (3, 10),
+                (7, 10),
+                (11, 10),
+                (15, 10),
+                (3, 14),
+                (7, 14),
+                (11, 14),
+                (15, 14),
+            ]
+            // Each badge takes 2x2 tiles
+            .map(|(col, row)| Position {
+                width: 2 * TILE_SIZE,
+                height: 2 * TILE_SIZE,
+                ..tile_field(col, row, 2)
+            }),
+        }
+    }
+
+    /// Reads the badges obtained, given the names of the badges of the page.
+    ///
+    /// Expects the image to be a binary image.
+    pub fn read_badges(
+        &self,
+        img: &GrayImage,
+        names: &[&'static str],
+    ) -> Result<Vec<&'static str>, String> {
+        if img.width() as i32 != self.width || img.height() as i32 != self.height {
+            return Err("Mismatch in image and layout dimensions.".to_string());
+        }
+        read_badges(img, &self.badges, names)
+    }
+
+    /// Returns the pixels of the faces and the badges, to tell whether two
+    /// scans show the same page.
+    ///
+    /// Expects the image to be a binary image.
+    pub fn read_leaders(&self, img: &GrayImage) -> Result<Vec<u8>, String> {
+        if img.width() as i32 != self.width || img.height() as i32 != self.height {
+            return Err("Mismatch in image and layout dimensions.".to_string());
+        }
+        Ok(Roi::new(img, self.leaders)?.iter().copied().collect())
+    }
+}
+
+impl Default for GscBadgePage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An item listed in the bag.
+#[derive(Debug, PartialEq, Clone)]
+pub struct BagItem {
+    pub name: String,
+    /// The quantity, None for the items shown without one, like key items.
+    pub quantity: Option<i32>,
+}
+
+/// The visible part of an item list of the bag.
+#[derive(Debug, PartialEq, Clone)]
+pub struct BagContent {
+    /// The items from the top of the list on the screen.
+    pub items: Vec<BagItem>,
+    /// The index of the item under the cursor, the number of items for the
+    /// "CANCEL" at the end.
+    pub cursor: usize,
+    /// True if the "CANCEL" at the end of the list is shown.
+    pub is_end: bool,
+}
+
+/// Reads the items of an item list.
+///
+/// The quantity of an item is shown on the row below its name, after the
+/// "×" char.
+fn read_bag_items(
+    img: &GrayImage,
+    chars: &CharTable,
+    cursors: &[Position],
+    names: &[Position],
+    quantities: &[Position],
+) -> Result<BagContent, String> {
+    let (names, is_end) = read_list_names(img, chars, names)?;
+    let mut items = Vec::new();
+    for (name, pos) in names.into_iter().zip(quantities) {
+        let quantity = read_text(img, pos, chars, &format!("quantity of '{name}'"))?;
+        let quantity = match quantity.as_str() {
+            "" => None,
+            _ => Some(quantity.parse::<i32>().map_err(|_| {
+                format!("could not parse quantity '{quantity}' of '{name}' to i32")
+            })?),
+        };
+        items.push(BagItem { name, quantity });
+    }
+
+    Ok(BagContent {
+        items,
+        cursor: find_cursor(img, cursors)?,
+        is_end,
+    })
+}
+
+/// Returns true if the first row of the item list shows an item or the "CANCEL".
+fn is_item_list(img: &GrayImage, chars: &CharTable, name: &Position, quantity: &Position) -> bool {
+    match read_field(img, name, chars) {
+        Ok(name) if name.trim() == "CANCEL" => true,
+        Ok(name) if !name.trim().is_empty() => read_field(img, quantity, chars)
+            .is_ok_and(|x| x.trim().is_empty() || x.trim().parse::<i32>().is_ok()),
+        _ => false,
+    }
+}
+
+/// The layout of the RBY bag.
+///
+/// The bag is a single list of four items at a time, with the TMs and HMs
+/// listed by their number, like "TM24".
+pub struct RbyBag {
+    pub width: i32,
+    pub height: i32,
+    /// The cursors in front of the items.
+    pub cursors: [Position; 4],
+    pub names: [Position; 4],
+    /// The quantity digits, after the "×" char.
+    pub quantities: [Position; 4],
+}
+
+impl RbyBag {
+    /// Creates a new instance of the RBY bag layout.
+    pub fn new() -> RbyBag {
+        let rows = [4, 6, 8, 10];
+        RbyBag {
+            width: 160,
+            height: 144,
+            cursors: rows.map(|row| tile_field(5, row, 1)),
+            names: rows.map(|row| tile_field(6, row, 12)),
+            quantities: rows.map(|row| tile_field(15, row + 1, 2)),
+        }
+    }
+
+    /// Returns true if the image is the RBY bag.
+    ///
+    /// Expects the image to be a binary image.
+    pub fn verify_layout(&self, img: &GrayImage, chars: &CharTable) -> bool {
+        if img.width() as i32 != self.width || img.height() as i32 != self.height {
+            return false;
+        }
+        is_item_list(img, chars, &self.names[0], &self.quantities[0])
+    }
+
+    /// Reads the visible items of the list from the screen.
+    ///
+    /// Expects the image to be a binary image.
+    pub fn read_fields(&self, img: &GrayImage, chars: &CharTable) -> Result<BagContent, String> {
+        if img.width() as i32 != self.width || img.height() as i32 != self.height {
+            return Err("Mismatch in image and layout dimensions.".to_string());
+        }
+        read_bag_items(img, chars, &self.cursors, &self.names, &self.quantities)
+    }
+}
+
+impl Default for RbyBag {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The layout of the GSC bag.
+///
+/// The bag has the pockets of the items, the balls, the key items and the
+/// TMs and HMs, each listing five items at a time.
+pub struct GscBag {
+    pub width: i32,
+    pub height: i32,
+    /// The cursors in front of the items.
+    pub cursors: [Position; 5],
+    pub names: [Position; 5],
+    /// The quantity digits, after the "×" char.
+    pub quantities: [Position; 5],
+}
+
+impl GscBag {
+    /// Creates a new instance of the GSC bag layout.
+    pub fn new() -> GscBag {
+        let rows = [2, 4, 6, 8, 10];
+        GscBag {
+            width: 160,
+            height: 144,
+            cursors: rows.map(|row| tile_field(4, row, 1)),
+            names: rows.map(|row| tile_field(5, row, 12)),
+            quantities: rows.map(|row| tile_field(16, row + 1, 2)),
+        }
+    }
+
+    /// Returns true if the image is the GSC bag.
+    ///
+    /// Expects the image to be a binary image.
+    pub fn verify_layout(&self, img: &GrayImage, chars: &CharTable) -> bool {
+        if img.width() as i32 != self.width || img.height() as i32 != self.height {
+            return false;
+        }
+        is_item_list(img, chars, &self.names[0], &self.quantities[0])
+    }
+
+    /// Reads the visible items of the pocket from the screen.
+    ///
+    /// Expects the image to be a binary image.
+    pub fn read_fields(&self, img: &GrayImage, chars: &CharTable) -> Result<BagContent, String> {
+        if img.width() as i32 != self.width || img.height() as i32 != self.height {
+            return Err("Mismatch in image and layout dimensions.".to_string());
+        }
+        read_bag_items(img, chars, &self.cursors, &self.names, &self.quantities)
+    }
+}
+
+impl Default for GscBag {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ocr::draw_text;
+    use crate::screen::{ScreenClassifier, ScreenKind};
+    use crate::session::ScanSession;
+    use crate::utils::{
+        scan_bag_with, scan_bag_with_session, scan_box_with_session, scan_img, scan_img_with,
+        scan_img_with_session, ScanOptions,
+    };
+    use image::imageops::FilterType;
+
+    const IMG_RBY: &[u8] = include_bytes!("../data/images/Yellow_summary_1.png");
+    const IMG_GSC: &[u8] = include_bytes!("../data/images/Crystal_summary_2.png");
+
+    #[test]
+    fn scan_gba_stretched_screen() {
+        for img in [IMG_RBY, IMG_GSC] {
+            let img = image::load_from_memory(img).unwrap();
+            let expected = scan_img(img.clone()).expect("could not scan original");
+
+            let stretched = img.resize_exact(240, 160, FilterType::Nearest);
+            let mut framed = RgbaImage::from_pixel(260, 180, Rgba([0, 0, 0, 255]));
+            overlay(&mut framed, &stretched.to_rgba8(), 10, 10);
+
+            let result = scan_img(DynamicImage::ImageRgba8(framed));
+            assert_eq!(result, Ok(expected));
+        }
+    }
+
+    #[test]
+    fn scan_cropped_screen() {
+        let img = image::load_from_memory(IMG_RBY).unwrap();
+        let expected = scan_img(img.clone()).expect("could not scan original");
+
+        let model = AspectModel::cropped(2, 3, 1, 2);
+        let visible = model.visible_region();
+        let cropped = img
+            .crop_imm(visible.x, visible.y, visible.width, visible.height)
+            .resize_exact(visible.width * 2, visible.height * 2, FilterType::Nearest);
+
+        let options = ScanOptions {
+            aspect_models: vec![model],
+            screen_pos: None,
+            font: None,
+        };
+        let result = scan_img_with(cropped, &options);
+        assert_eq!(result, Ok(expected));
+    }
+    #[test]
+    fn read_rby_summary_1() {
+        let img = image::load_from_memory(IMG_RBY).unwrap();
+        let img = normalize_palette(&img);
+        let chars = CharTable::new();
+
+        let content = RbySummary1::new().read_fields(&img, &chars).unwrap();
+        assert_eq!(content.ndex, 25);
+        assert_eq!(content.nickname.as_deref(), Some("PIKACHU"));
+        assert_eq!(content.status.as_deref(), Some("OK"));
+        assert_eq!(content.type1.as_deref(), Some("ELECTRIC"));
+        assert_eq!(content.type2, None);
+        assert_eq!(content.id, Some(5275));
+        assert_eq!(content.ot.as_deref(), Some("RED"));
+
+        let pokedex = crate::pokemon::RbyPokedex::new();
+        assert!(content.verify_types(pokedex.get_ndex(25).unwrap()).is_ok());
+        assert!(content.verify_types(pokedex.get_ndex(6).unwrap()).is_err());
+    }
+
+    /// Fills the tile at the tile coordinates, so it can not be read.
+    fn blot_tile(img: &mut GrayImage, col: u32, row: u32) {
+        for y in row * 8..row * 8 + 7 {
+            for x in col * 8..col * 8 + 7 {
+                img.put_pixel(x, y, Luma([255]));
+            }
+        }
+    }
+
+    #[test]
+    fn read_rby_summary_1_unreadable_fields() {
+        let img = image::load_from_memory(IMG_RBY).unwrap();
+        let mut img = normalize_palette(&img);
+        let chars = CharTable::new();
+        let layout = RbySummary1::new();
+        for pos in [
+            &layout.nickname_field_pos,
+            &layout.type1_field_pos,
+            &layout.ot_field_pos,
+        ] {
+            blot_tile(&mut img, pos.x / 8, pos.y / 8);
+        }
+
+        let content = layout.read_fields(&img, &chars).unwrap();
+        assert_eq!(content.nickname, None);
+        assert_eq!((content.type1, content.type2), (None, None));
+        assert_eq!(content.ot, None);
+        assert_eq!(content.id, Some(5275));
+        assert_eq!(content.special, 11);
+
+        invert(&mut img);
+        let report = scan_img_with(DynamicImage::ImageLuma8(img), &native_screen()).unwrap();
+        assert!(report.starts_with("No.25 Pikachu :L"));
+        assert!(report.ends_with("\nCheck failed: could not read the types\n"));
+    }
+
+    #[test]
+    fn read_gsc_summaries() {
+        let chars = CharTable::new();
+        let read = |bytes| normalize_palette(&image::load_from_memory(bytes).unwrap());
+
+        let img = read(include_bytes!("../data/images/Crystal_summary_1.png"));
+        let content = GscSummary1::new().read_fields(&img, &chars).unwrap();
+        assert_eq!((content.ndex, content.level), (158, 5));
+        assert_eq!(content.gender, Some(Gender::Male));
+        assert!(!content.shiny);
+        assert_eq!(content.nickname.as_deref(), Some("TOTODILE"));
+        assert_eq!(content.status.as_deref(), Some("OK"));
+        assert_eq!(content.pokerus, Some(false));
+        assert_eq!(content.type1.as_deref(), Some("WATER"));
+        assert_eq!(content.type2, None);
+        assert_eq!((content.exp, content.exp_to_next), (Some(135), Some(44)));
+
+        // The fields besides the HP are not needed for the DVs
+        let mut blotted = img.clone();
+        let layout = GscSummary1::new();
+        for pos in [&layout.nickname, &layout.status, &layout.type1, &layout.exp] {
+            blot_tile(&mut blotted, pos.x / 8, pos.y / 8);
+        }
+        let content = layout.read_fields(&blotted, &chars).unwrap();
+        assert_eq!((content.nickname, content.status), (None, None));
+        assert_eq!((content.type1, content.exp), (None, None));
+        assert_eq!(content.hp, 20);
+
+        // The star is matched against its glyph, a blotted tile is no star
+        let mut marked = img.clone();
+        draw_text(&mut marked, "★", layout.shiny.x, layout.shiny.y, &chars);
+        blot_tile(&mut marked, layout.gender.x / 8, layout.gender.y / 8);
+        let content = layout.read_fields(&marked, &chars).unwrap();
+        assert_eq!((content.gender, content.shiny), (None, true));
+        blot_tile(&mut marked, layout.shiny.x / 8, layout.shiny.y / 8);
+        let content = layout.read_fields(&marked, &chars).unwrap();
+        assert!(!content.shiny);
+
+        let img = read(IMG_GSC);
+        let layout = GscSummary2::new();
+        let content = layout.read_fields(&img, &chars).unwrap();
+        assert_eq!(content.item.as_deref(), Some("BERRY"));
+        assert_eq!(content.move_1.as_deref(), Some("SCRATCH"));
+
+        let mut blotted = img.clone();
+        for pos in [&layout.item, &layout.move_2] {
+            blot_tile(&mut blotted, pos.x / 8, pos.y / 8);
+        }
+        let content = layout.read_fields(&blotted, &chars).unwrap();
+        assert_eq!((content.item, content.move_2), (None, None));
+        assert_eq!(content.move_1.as_deref(), Some("SCRATCH"));
+
+        let img = read(include_bytes!("../data/images/Crystal_summary_3.png"));
+        let content = GscSummary3::new().read_fields(&img, &chars).unwrap();
+        assert_eq!(content.id, Some(61013));
+        assert_eq!(content.ot.as_deref(), Some("KRIS"));
+        assert_eq!(content.attack, 12);
+
+        let mut blotted = img.clone();
+        let layout = GscSummary3::new();
+        for pos in [&layout.id, &layout.ot] {
+            blot_tile(&mut blotted, pos.x / 8, pos.y / 8);
+        }
+        let content = layout.read_fields(&blotted, &chars).unwrap();
+        assert_eq!((content.id, content.ot), (None, None));
+        assert_eq!(content.speed, 10);
+    }
+
+    #[test]
+    fn read_rby_summary_2() {
+        let img = image::load_from_memory(include_bytes!("../data/images/Yellow_summary_2.png"));
+        let img = normalize_palette(&img.unwrap());
+        let chars = CharTable::new();
+
+        let content = RbySummary2::new().read_fields(&img, &chars).unwrap();
+        assert_eq!(content.ndex, "025");
+        assert_eq!((content.exp, content.exp_to_next), (125, 91));
+        assert_eq!(content.next_level, 6);
+        assert_eq!(content.level(), 5);
+    }
+    /// Draws a HP bar of 6 tiles, framed above and below its filling.
+    fn draw_hp_bar(img: &mut GrayImage, x: u32, y: u32, fill: u32) {
+        for dx in 0..48 {
+            img.put_pixel(x + dx, y + 2, Luma([255]));
+            img.put_pixel(x + dx, y + 6, Luma([255]));
+        }
+        for dx in 0..fill {
+            for dy in 3..6 {
+                img.put_pixel(x + dx, y + dy, Luma([255]));
+            }
+        }
+    }
+
+    /// Draws the RBY party menu with Pikachu and a poisoned Pidgey.
+    fn draw_rby_party(chars: &CharTable) -> GrayImage {
+        let mut img = GrayImage::new(160, 144);
+        draw_text(&mut img, "PIKACHU", 24, 0, chars);
+        draw_text(&mut img, "12", 112, 0, chars);
+        draw_hp_bar(&mut img, 48, 8, 24);
+        draw_text(&mut img, " 17", 104, 8, chars);
+        draw_text(&mut img, "/", 128, 9, chars);
+        draw_text(&mut img, " 35", 136, 8, chars);
+        draw_text(&mut img, "PIDGEY", 24, 16, chars);
+        draw_text(&mut img, "00", 112, 16, chars);
+        draw_text(&mut img, "PSN", 136, 16, chars);
+        draw_hp_bar(&mut img, 48, 24, 48);
+        draw_text(&mut img, "250", 104, 24, chars);
+        draw_text(&mut img, "/", 128, 25, chars);
+        draw_text(&mut img, "250", 136, 24, chars);
+        draw_text(&mut img, "Choose a POKéMON.", 8, 112, chars);
+        img
+    }
+
+    #[test]
+    fn read_rby_party() {
+        let chars = CharTable::new();
+        let img = draw_rby_party(&chars);
+
+        let layout = RbyParty::new();
+        assert!(layout.verify_layout(&img, &chars));
+        assert!(!GscParty::new().verify_layout(&img, &chars));
+
+        let party = layout.read_fields(&img, &chars).unwrap();
+        assert_eq!(party.len(), 2);
+        assert_eq!(
+            party[0],
+            PartySlot {
+                nickname: "PIKACHU".to_string(),
+                level: 12,
+                hp: 17,
+                max_hp: 35,
+                status: "OK".to_string(),
+                hp_bar_fill: 0.5,
+            }
+        );
+        assert_eq!((party[1].level, party[1].hp), (100, 250));
+        assert_eq!(party[1].status, "PSN");
+        assert_eq!(party[1].hp_bar_fill, 1.0);
+    }
+
+    #[test]
+    fn read_gsc_party() {
+        let chars = CharTable::new();
+        let mut img = GrayImage::new(160, 144);
+        draw_text(&mut img, "TOTODILE", 24, 8, &chars);
+        draw_text(&mut img, "  0/ 22", 104, 8, &chars);
+        draw_text(&mut img, "FNT", 40, 16, &chars);
+        draw_text(&mut img, "7", 72, 16, &chars);
+        draw_hp_bar(&mut img, 104, 16, 0);
+        draw_text(&mut img, "Choose a POKéMON.", 8, 128, &chars);
+
+        let layout = GscParty::new();
+        assert!(layout.verify_layout(&img, &chars));
+        assert!(!RbyParty::new().verify_layout(&img, &chars));
+        let tilemap = TileMap::read(&img, &chars).unwrap();
+        let classification = ScreenClassifier::new().classify(&tilemap);
+        assert_eq!(classification.map(|c| c.kind), Some(ScreenKind::GscParty));
+
+        let party = layout.read_fields(&img, &chars).unwrap();
+        assert_eq!(party.len(), 1);
+        assert_eq!(party[0].nickname, "TOTODILE");
+        assert_eq!((party[0].level, party[0].hp, party[0].max_hp), (7, 0, 22));
+        assert_eq!(party[0].status, "FNT");
+        assert_eq!(party[0].hp_bar_fill, 0.0);
+    }
+
+    #[test]
+    fn scan_party_report() {
+        let mut img = draw_rby_party(&CharTable::new());
+        invert(&mut img);
+
+        let report = scan_img_with(DynamicImage::ImageLuma8(img), &native_screen()).unwrap();
+        assert_eq!(
+            report,
+            "1 PIKACHU (No.25 Pikachu) :L12  HP 17/35\n\
+             2 PIDGEY (No.16 Pidgey) :L100  HP 250/250  PSN\n"
+        );
+    }
+    /// Returns the options of scanning an image of the native screen only.
+    fn native_screen() -> ScanOptions {
+        ScanOptions {
+            aspect_models: vec![AspectModel::native()],
+            screen_pos: Some(Position {
+                x: 0,
+                y: 0,
+                width: 160,
+                height: 144,
+            }),
+            font: None,
+        }
+    }
+
+    /// Draws a RBY battle against a wild Pidgey with half of its HP.
+    fn draw_rby_battle(chars: &CharTable) -> GrayImage {
+        let mut img = GrayImage::new(160, 144);
+        draw_text(&mut img, "PIDGEY", 8, 0, chars);
+        draw_text(&mut img, "5", 40, 8, chars);
+        draw_hp_bar(&mut img, 32, 16, 24);
+        draw_text(&mut img, "ONIX", 80, 56, chars);
+        draw_text(&mut img, "PAR", 120, 64, chars);
+        draw_hp_bar(&mut img, 96, 72, 48);
+        draw_text(&mut img, " 42", 88, 80, chars);
+        draw_text(&mut img, "/", 112, 81, chars);
+        draw_text(&mut img, " 42", 120, 80, chars);
+        draw_text(&mut img, "FIGHT", 80, 112, chars);
+        draw_text(&mut img, "ITEM  RUN", 80, 128, chars);
+        img
+    }
+
+    #[test]
+    fn read_rby_battle() {
+        let chars = CharTable::new();
+        let img = draw_rby_battle(&chars);
+
+        let layout = RbyBattle::new();
+        assert!(layout.verify_layout(&img, &chars));
+        assert!(!GscBattle::new().verify_layout(&img, &chars));
+
+        let content = layout.read_fields(&img, &chars).unwrap();
+        assert_eq!(content.opponent.name, "PIDGEY");
+        assert_eq!(content.opponent.level, Some(5));
+        assert_eq!(content.opponent.status, "OK");
+        assert_eq!(content.opponent.hp_bar_pixels, 24);
+        assert_eq!(content.player.name, "ONIX");
+        assert_eq!(content.player.level, None);
+        assert_eq!(content.player.status, "PAR");
+        assert_eq!((content.hp, content.max_hp), (42, 42));
+
+        let pokedex = crate::pokemon::RbyPokedex::new();
+        let pidgey = pokedex.get_ingame(&content.opponent.name).unwrap();
+        assert_eq!(
+            content.opponent.hp(pidgey.hp),
+            Ok(StatRange { min: 10, max: 10 })
+        );
+        assert!(content.player.hp(35).is_err());
+    }
+
+    #[test]
+    fn read_gsc_battle() {
+        let chars = CharTable::new();
+        let mut img = GrayImage::new(160, 144);
+        draw_text(&mut img, "SENTRET", 8, 0, &chars);
+        draw_text(&mut img, "00♀", 56, 8, &chars);
+        draw_hp_bar(&mut img, 32, 16, 1);
+        draw_text(&mut img, "MAGNEMITE", 80, 56, &chars);
+        draw_text(&mut img, "12", 112, 64, &chars); // Genderless
+        draw_hp_bar(&mut img, 96, 72, 30);
+        draw_text(&mut img, " 19/ 30", 88, 80, &chars);
+        draw_text(&mut img, "FIGHT", 80, 112, &chars);
+        draw_text(&mut img, "PACK  RUN", 80, 128, &chars);
+
+        let layout = GscBattle::new();
+        assert!(layout.verify_layout(&img, &chars));
+        assert!(!RbyBattle::new().verify_layout(&img, &chars));
+        let tilemap = TileMap::read(&img, &chars).unwrap();
+        let classification = ScreenClassifier::new().classify(&tilemap);
+        assert_eq!(classification.map(|c| c.kind), Some(ScreenKind::GscBattle));
+
+        let content = layout.read_fields(&img, &chars).unwrap();
+        assert_eq!(content.opponent.level, Some(100));
+        assert_eq!(content.opponent.gender, Some(Gender::Female));
+        assert_eq!(content.opponent.hp_bar_pixels, 1);
+        assert_eq!(content.player.name, "MAGNEMITE");
+        assert_eq!(
+            (content.player.level, content.player.gender),
+            (Some(12), None)
+        );
+        assert_eq!((content.hp, content.max_hp), (19, 30));
+
+        let pokedex = crate::pokemon::GscPokedex::new();
+        let sentret = pokedex.get_ingame(&content.opponent.name).unwrap();
+        let hp = content.opponent.hp(sentret.hp).unwrap();
+        assert_eq!(hp, StatRange { min: 1, max: 8 });
+
+        // The DVs of trainers are unknown in GSC
+        invert(&mut img);
+        let report = scan_img_with(DynamicImage::ImageLuma8(img), &native_screen()).unwrap();
+        assert!(report.starts_with("Enemy No.161 Sentret♀ :L100\n"));
+        assert!(report.contains("Stat  Base   Min   Max\n"));
+        assert!(!report.contains("Trainer"));
+    }
+
+    #[test]
+    fn scan_rby_battle_report() {
+        let mut img = draw_rby_battle(&CharTable::new());
+        invert(&mut img); // Dark text on a light background, like the game
+
+        let report = scan_img_with(DynamicImage::ImageLuma8(img), &native_screen()).unwrap();
+        assert!(report.starts_with("Enemy No.16 Pidgey :L5\n"));
+        assert!(report.contains("Weak to: Electric x2, Ice x2, Rock x2\n"));
+        assert!(report.contains("HP: 10 - 10 of 19 - 20\n"));
+        assert!(report.contains("L5    Sand-Attack\n"));
+        assert!(report.contains("Stat  Base   Min   Max  Trainer\n"));
+    }
+    /// Draws a cursor, a filled triangle pointing right.
+    fn draw_cursor(img: &mut GrayImage, x: u32, y: u32) {
+        for dy in 0..7 {
+            for dx in 0..(4 - (dy as i32 - 3).abs()) as u32 {
+                img.put_pixel(x + dx, y + dy, Luma([255]));
+            }
+        }
+    }
+
+    /// Draws the RBY FIGHT menu with Thunder Shock under the cursor.
+    fn draw_rby_fight_menu(chars: &CharTable) -> GrayImage {
+        let mut img = GrayImage::new(160, 144);
+        draw_text(&mut img, "TYPE", 8, 72, chars);
+        draw_text(&mut img, "/", 40, 73, chars);
+        draw_text(&mut img, "ELECTRIC", 16, 80, chars);
+        draw_text(&mut img, "25", 40, 88, chars);
+        draw_text(&mut img, "/", 56, 89, chars);
+        draw_text(&mut img, "36", 64, 88, chars);
+        draw_text(&mut img, "GROWL", 48, 104, chars);
+        draw_text(&mut img, "THUNDERSHOCK", 48, 112, chars);
+        draw_text(&mut img, "-", 48, 120, chars);
+        draw_text(&mut img, "-", 48, 128, chars);
+        draw_cursor(&mut img, 40, 112);
+        img
+    }
+
+    #[test]
+    fn read_fight_menus() {
+        let chars = CharTable::new();
+        let mut img = draw_rby_fight_menu(&chars);
+
+        let layout = RbyFightMenu::new();
+        assert!(layout.verify_layout(&img, &chars));
+        assert!(!GscFightMenu::new().verify_layout(&img, &chars));
+        let classify = |img: &GrayImage| {
+            let tilemap = TileMap::read(img, &chars).unwrap();
+            ScreenClassifier::new().classify(&tilemap).map(|c| c.kind)
+        };
+        assert_eq!(classify(&img), Some(ScreenKind::RbyFightMenu));
+
+        let content = layout.read_fields(&img, &chars).unwrap();
+        assert_eq!(content.cursor, 1);
+        assert_eq!(content.selected_move(), "THUNDERSHOCK");
+        assert_eq!(content.moves[2], "-");
+        assert_eq!(content.type_, "ELECTRIC");
+        assert_eq!((content.pp, content.max_pp), (25, 36));
+
+        // The same menu in GSC, with the "/" a pixel higher
+        for y in 88..96 {
+            for x in 56..64 {
+                img.put_pixel(x, y, Luma([0]));
+            }
+        }
+        for y in 72..80 {
+            for x in 40..48 {
+                img.put_pixel(x, y, Luma([0]));
+            }
+        }
+        draw_text(&mut img, "/", 56, 88, &chars);
+        draw_text(&mut img, "/", 40, 72, &chars);
+        assert!(!layout.verify_layout(&img, &chars));
+        assert!(GscFightMenu::new().verify_layout(&img, &chars));
+        assert_eq!(classify(&img), Some(ScreenKind::GscFightMenu));
+    }
+
+    #[test]
+    fn scan_fight_menu_report() {
+        let mut img = draw_rby_fight_menu(&CharTable::new());
+        invert(&mut img);
+
+        let report = scan_img_with(DynamicImage::ImageLuma8(img), &native_screen()).unwrap();
+        assert!(report.starts_with("THUNDERSHOCK  PP 25/36\n1 PP Up(s) used, base PP 30\n"));
+        assert!(!report.contains("Check failed"));
+    }
+    /// Draws the RBY level-up stats box of a Pikachu growing to level 12.
+    fn draw_rby_level_up(chars: &CharTable, name: &str) -> GrayImage {
+        let mut img = GrayImage::new(160, 144);
+        let stats = [
+            ("ATTACK", " 20"),
+            ("DEFENSE", " 14"),
+            ("SPEED", " 28"),
+            ("SPECIAL", " 18"),
+        ];
+        for (idx, (label, value)) in stats.iter().enumerate() {
+            let y = 24 + idx as u32 * 16;
+            draw_text(&mut img, label, 88, y, chars);
+            draw_text(&mut img, value, 120, y + 8, chars);
+        }
+        draw_text(&mut img, &format!("{name} grew"), 8, 112, chars);
+        draw_text(&mut img, "to level 12!", 8, 128, chars);
+        img
+    }
+
+    #[test]
+    fn read_level_up_stats() {
+        let chars = CharTable::new();
+        let img = draw_rby_level_up(&chars, "PIKACHU");
+
+        let layout = RbyLevelUp::new();
+        assert!(layout.verify_layout(&img, &chars));
+        assert!(!GscLevelUp::new().verify_layout(&img, &chars));
+
+        let content = layout.read_fields(&img, &chars).unwrap();
+        assert_eq!(
+            content,
+            RbyLevelUpContent {
+                name: "PIKACHU".to_string(),
+                level: 12,
+                attack: 20,
+                defense: 14,
+                speed: 28,
+                special: 18,
+            }
+        );
+    }
+
+    #[test]
+    fn scan_level_up_report() {
+        let mut img = draw_rby_level_up(&CharTable::new(), "PIKACHU");
+        invert(&mut img);
+
+        let report = scan_img_with(DynamicImage::ImageLuma8(img), &native_screen()).unwrap();
+        assert!(report.starts_with("No.25 Pikachu :L12\n"));
+        // The stat exp. is unknown, so any DV giving the stat with some is possible
+        assert!(report.contains(" ATT    55     20    0 - 11\n"));
+
+        // The species of a nickname is known from the summary
+        let mut img = draw_rby_level_up(&CharTable::new(), "SPARKY");
+        invert(&mut img);
+        let img = DynamicImage::ImageLuma8(img);
+        let mut session = ScanSession::new();
+        let report = scan_img_with_session(img.clone(), &native_screen(), &mut session);
+        assert!(report.is_err());
+
+        session.record_species("SPARKY", "Pikachu");
+        let report = scan_img_with_session(img, &native_screen(), &mut session).unwrap();
+        assert!(report.starts_with("No.25 Pikachu :L12\n"));
+    }
+    /// Draws the RBY Pokédex entry of Pikachu.
+    fn draw_rby_pokedex_entry(chars: &CharTable) -> GrayImage {
+        let mut img = GrayImage::new(160, 144);
+        draw_text(&mut img, "PIKACHU", 72, 16, chars);
+        draw_text(&mut img, "MOUSE", 72, 32, chars);
+        draw_text(&mut img, "HT  1 04", 72, 48, chars);
+        draw_text(&mut img, "025", 32, 64, chars);
+        draw_text(&mut img, "WT  13.0lb", 72, 64, chars);
+        draw_text(&mut img, "When several of", 8, 88, chars);
+        draw_text(&mut img, "them gather, their", 8, 104, chars);
+        draw_text(&mut img, "electricity could", 8, 120, chars);
+        img
+    }
+
+    #[test]
+    fn read_pokedex_entries() {
+        let chars = CharTable::new();
+        let img = draw_rby_pokedex_entry(&chars);
+
+        let layout = RbyPokedexEntry::new();
+        assert!(layout.verify_layout(&img, &chars));
+        assert!(!GscPokedexEntry::new().verify_layout(&img, &chars));
+
+        let content = layout.read_fields(&img, &chars).unwrap();
+        assert_eq!(
+            content,
+            PokedexEntryContent {
+                ndex: 25,
+                name: "PIKACHU".to_string(),
+                category: "MOUSE".to_string(),
+                height: Some((1, 4)),
+                weight: Some(13.0),
+                description: "When several of them gather, their electricity could".to_string(),
+            }
+        );
+
+        // Not caught yet
+        let mut img = GrayImage::new(160, 144);
+        draw_text(&mut img, "CHIKORITA", 72, 24, &chars);
+        draw_text(&mut img, "LEAF", 72, 40, &chars);
+        draw_text(&mut img, "HT  ? ??", 72, 56, &chars);
+        draw_text(&mut img, "152", 32, 64, &chars);
+        draw_text(&mut img, "WT   ???lb", 72, 72, &chars);
+
+        let layout = GscPokedexEntry::new();
+        assert!(layout.verify_layout(&img, &chars));
+        let content = layout.read_fields(&img, &chars).unwrap();
+        assert_eq!((content.ndex, content.category.as_str()), (152, "LEAF"));
+        assert_eq!((content.height, content.weight), (None, None));
+        assert_eq!(content.description, "");
+    }
+
+    #[test]
+    fn scan_pokedex_entry_report() {
+        let mut img = draw_rby_pokedex_entry(&CharTable::new());
+        invert(&mut img);
+
+        let report = scan_img_with(DynamicImage::ImageLuma8(img), &native_screen()).unwrap();
+        assert!(report.starts_with(
+            "No.25 Pikachu\nMOUSE Pokémon\nHT 1'04\"  WT 13.0 lb\n\
+            When several of them gather, their electricity could\nType: Electric\n"
+        ));
+        assert!(!report.contains("Check failed"));
+        assert!(report.contains("\nEvo chain(s)\nPikachu   ->   Thunder Stone   ->   Raichu\n"));
+        assert!(report.contains("\nNo.25 Pikachu learnset\n"));
+        assert!(report.contains("\nNo.26 Raichu learnset\n"));
+        assert!(report.contains("\n9    8    Thunder Wave "));
+    }
+    #[test]
+    fn read_box_lists() {
+        let chars = CharTable::new();
+
+        // Scrolled to the end of the box
+        let mut img = GrayImage::new(160, 144);
+        draw_text(&mut img, "PIDGEY", 48, 32, &chars);
+        draw_text(&mut img, "12", 96, 40, &chars);
+        draw_text(&mut img, "SPARKY", 48, 48, &chars);
+        draw_text(&mut img, "00", 96, 56, &chars);
+        draw_text(&mut img, "CANCEL", 48, 64, &chars);
+        draw_cursor(&mut img, 40, 48);
+
+        let layout = RbyBoxList::new();
+        assert!(layout.verify_layout(&img, &chars));
+        let content = layout.read_fields(&img, &chars).unwrap();
+        assert_eq!(
+            content.entries,
+            [
+                BoxEntry {
+                    name: "PIDGEY".to_string(),
+                    level: Some(12),
+                    species: None,
+                },
+                BoxEntry {
+                    name: "SPARKY".to_string(),
+                    level: Some(MAX_LEVEL),
+                    species: None,
+                },
+            ]
+        );
+        assert_eq!((content.cursor, content.is_end), (1, true));
+
+        let mut img = GrayImage::new(160, 144);
+        for (idx, name) in ["PIDGEY", "SPARKY", "RATTATA", "ODDISH", "ZUBAT"]
+            .iter()
+            .enumerate()
+        {
+            draw_text(&mut img, name, 72, 32 + idx as u32 * 16, &chars);
+        }
+        draw_text(&mut img, "PIKACHU", 8, 112, &chars);
+        draw_text(&mut img, "35", 8, 96, &chars);
+        draw_cursor(&mut img, 64, 48);
+
+        let layout = GscBoxList::new();
+        assert!(layout.verify_layout(&img, &chars));
+        let content = layout.read_fields(&img, &chars).unwrap();
+        assert_eq!(content.entries.len(), 5);
+        assert_eq!(
+            content.entries[1],
+            BoxEntry {
+                name: "SPARKY".to_string(),
+                level: Some(35),
+                species: Some("PIKACHU".to_string()),
+            }
+        );
+        assert_eq!(content.entries[0].level, None);
+        assert!(!content.is_end);
+    }
+
+    /// Draws the RBY box list with the Pokémon and their levels.
+    fn draw_rby_box_list(entries: &[(&str, &str)], is_end: bool) -> GrayImage {
+        let chars = CharTable::new();
+        let mut img = GrayImage::new(160, 144);
+        for (idx, (name, level)) in entries.iter().enumerate() {
+            let y = 32 + idx as u32 * 16;
+            draw_text(&mut img, name, 48, y, &chars);
+            draw_text(&mut img, level, 96, y + 8, &chars);
+        }
+        if is_end {
+            draw_text(
+                &mut img,
+                "CANCEL",
+                48,
+                32 + entries.len() as u32 * 16,
+                &chars,
+            );
+        }
+        draw_cursor(&mut img, 40, 32);
+        invert(&mut img);
+        img
+    }
+
+    #[test]
+    fn scan_box_report() {
+        let mut session = ScanSession::new();
+        let mut scan = |img: GrayImage| {
+            let img = DynamicImage::ImageLuma8(img);
+            scan_box_with_session(img, &native_screen(), &mut session).unwrap()
+        };
+
+        let top = [
+            ("PIDGEY", "12"),
+            ("SPARKY", "35"),
+            ("RATTATA", " 3"),
+            ("ODDISH", " 9"),
+        ];
+        assert_eq!(
+            scan(draw_rby_box_list(&top, false)),
+            "Box, 4 Pokémon scanned, scroll down for more\n\
+            1 PIDGEY :L12\n2 SPARKY :L35\n3 RATTATA :L3\n4 ODDISH :L9\n"
+        );
+
+        let end = [("RATTATA", " 3"), ("ODDISH", " 9"), ("ZUBAT", " 7")];
+        let report = scan(draw_rby_box_list(&end, true));
+        assert!(report.starts_with("Box, 5 Pokémon scanned\n1 PIDGEY :L12\n"));
+        assert!(report.ends_with("4 ODDISH :L9\n5 ZUBAT :L7\n"));
+
+        // Another box
+        let report = scan(draw_rby_box_list(&[("ONIX", "14")], true));
+        assert_eq!(
+            report,
+            "New box started\nBox, 1 Pokémon scanned\n1 ONIX :L14\n"
+        );
+    }
+    /// Draws the RBY trainer card of the player.
+    fn draw_rby_trainer_card(chars: &CharTable, name: &str) -> GrayImage {
+        let mut img = GrayImage::new(160, 144);
+        draw_text(&mut img, "NAME", 16, 16, chars);
+        draw_text(&mut img, "/", 48, 17, chars);
+        draw_text(&mut img, name, 56, 16, chars);
+        draw_text(&mut img, "MONEY", 16, 32, chars);
+        draw_text(&mut img, "/", 56, 33, chars);
+        draw_text(&mut img, "3175", 72, 32, chars);
+        draw_text(&mut img, "TIME", 16, 48, chars);
+        draw_text(&mut img, "/", 48, 49, chars);
+        draw_text(&mut img, "12:05", 72, 48, chars);
+        img
+    }
+
+    /// Draws the first page of the GSC trainer card of GOLD.
+    fn draw_gsc_trainer_card(chars: &CharTable) -> GrayImage {
+        let mut img = GrayImage::new(160, 144);
+        draw_text(&mut img, "NAME/GOLD", 16, 16, chars);
+        draw_text(&mut img, "01234", 40, 32, chars);
+        draw_text(&mut img, "MONEY", 16, 48, chars);
+        draw_text(&mut img, "500000", 64, 48, chars);
+        draw_text(&mut img, "PLAY TIME", 16, 96, chars);
+        draw_text(&mut img, "  0:42", 96, 96, chars);
+        img
+    }
+
+    /// Draws a badge page of the GSC trainer card with the badges of the
+    /// given slots, and a face at the given x to tell the pages apart.
+    fn draw_gsc_badge_page(chars: &CharTable, face_x: u32, slots: &[usize]) -> GrayImage {
+        let mut img = GrayImage::new(160, 144);
+        draw_text(&mut img, "NAME/GOLD", 16, 16, chars);
+        for (dx, dy) in (0..4).flat_map(|dx| (0..4).map(move |dy| (dx, dy))) {
+            img.put_pixel(face_x + dx, 72 + dy, Luma([255]));
+        }
+        for slot in slots {
+            let (x, y) = (24 + *slot as u32 % 4 * 32, 80 + *slot as u32 / 4 * 32);
+            for (dx, dy) in (0..12).flat_map(|dx| (0..12).map(move |dy| (dx, dy))) {
+                img.put_pixel(x + 2 + dx, y + 2 + dy, Luma([255]));
+            }
+        }
+        img
+    }
+
+    #[test]
+    fn read_trainer_cards() {
+        let chars = CharTable::new();
+        let img = draw_rby_trainer_card(&chars, "RED");
+
+        let layout = RbyTrainerCard::new();
+        assert!(layout.verify_layout(&img, &chars));
+        assert!(!GscTrainerCard::new().verify_layout(&img, &chars));
+
+        let content = layout.read_fields(&img, &chars).unwrap();
+        assert_eq!(
+            content,
+            TrainerCardContent {
+                name: "RED".to_string(),
+                id: None,
+                money: 3175,
+                play_time: (12, 5),
+            }
+        );
+        // The RBY card shows no ID, so only the OT is compared
+        assert!(!content.is_traded("RED", Some(12345)));
+        assert!(content.is_traded("BLUE", Some(12345)));
+
+        let img = draw_gsc_trainer_card(&chars);
+        let layout = GscTrainerCard::new();
+        assert!(layout.verify_layout(&img, &chars));
+        let content = layout.read_fields(&img, &chars).unwrap();
+        assert_eq!(content.id, Some(1234));
+        assert_eq!((content.money, content.play_time), (500000, (0, 42)));
+        assert!(!content.is_traded("GOLD", Some(1234)));
+        assert!(!content.is_traded("GOLD", None));
+        assert!(content.is_traded("GOLD", Some(4321)));
+
+        assert_eq!(
+            parse_badges("boulder, Soul", &RBY_BADGES),
+            Ok(vec!["Boulder", "Soul"])
+        );
+        assert_eq!(parse_badges("", &RBY_BADGES), Ok(vec![]));
+        assert!(parse_badges("Zephyr", &RBY_BADGES).is_err());
+
+        let img = draw_gsc_badge_page(&chars, 8, &[7]);
+        let badges = GscBadgePage::new().read_badges(&img, &JOHTO_BADGES);
+        assert_eq!(badges, Ok(vec!["Rising"]));
+    }
+
+    #[test]
+    fn classify_trainer_cards() {
+        let chars = CharTable::new();
+        let classifier = ScreenClassifier::new();
+        let classify = |img: &GrayImage| {
+            let tilemap = TileMap::read(img, &chars).unwrap();
+            classifier.classify(&tilemap).map(|x| x.kind)
+        };
+
+        let img = draw_gsc_badge_page(&chars, 8, &[0]);
+        assert_eq!(classify(&img), Some(ScreenKind::GscBadgePage));
+
+        // A card with a misread char is no badge page, which shows its name
+        let mut img = draw_gsc_trainer_card(&chars);
+        assert_eq!(classify(&img), Some(ScreenKind::GscTrainerCard));
+        blot_tile(&mut img, 2, 6); // The "M" of "MONEY"
+        assert_eq!(classify(&img), Some(ScreenKind::GscTrainerCard));
+
+        let mut img = draw_rby_trainer_card(&chars, "RED");
+        assert_eq!(classify(&img), Some(ScreenKind::RbyTrainerCard));
+        blot_tile(&mut img, 2, 4);
+        assert_eq!(classify(&img), Some(ScreenKind::RbyTrainerCard));
+    }
+
+    #[test]
+    fn scan_trainer_card_report() {
+        let scan = |mut img: GrayImage, session: &mut ScanSession| {
+            invert(&mut img);
+            scan_img_with_session(DynamicImage::ImageLuma8(img), &native_screen(), session)
+        };
+        let summary = || image::load_from_memory(IMG_RBY).unwrap();
+        let chars = CharTable::new();
+        let mut session = ScanSession::new();
+
+        let report = scan(draw_rby_trainer_card(&chars, "RED"), &mut session).unwrap();
+        assert_eq!(report, "Trainer RED\nMoney: 3175\nPlay time: 12:05\n");
+
+        // The Pikachu of RED, with its stats boosted by the badges in battle
+        session.record_badges(&RBY_BADGES, &["Boulder", "Cascade", "Soul"]);
+        let report = scan_img_with_session(summary(), &ScanOptions::default(), &mut session);
+        let report = report.unwrap();
+        assert!(!report.contains("Traded"));
+        assert!(report.contains(
+            "\nIn battle, boosted by the badges\nATT   11 ->  12  Boulder\nSPD   15 ->  16  Soul\n"
+        ));
+
+        scan(draw_rby_trainer_card(&chars, "BLUE"), &mut session).unwrap();
+        let report = scan_img_with_session(summary(), &ScanOptions::default(), &mut session);
+        assert!(report.unwrap().contains("\nTraded, gains boosted EXP.\n"));
+    }
+
+    #[test]
+    fn scan_badge_page_report() {
+        let scan = |mut img: GrayImage, session: &mut ScanSession| {
+            invert(&mut img);
+            scan_img_with_session(DynamicImage::ImageLuma8(img), &native_screen(), session)
+        };
+        let chars = CharTable::new();
+        let mut session = ScanSession::new();
+
+        // Without the first page, the pages can not be told apart
+        let report = scan(draw_gsc_badge_page(&chars, 8, &[0, 1]), &mut session);
+        assert!(report.is_err());
+
+        let report = scan(draw_gsc_trainer_card(&chars), &mut session).unwrap();
+        assert!(report.starts_with("Trainer GOLD\nID No. 01234\n"));
+
+        // The Pokémon of KRIS was traded to GOLD
+        let summary = include_bytes!("../data/images/Crystal_summary_3.png");
+        let summary = image::load_from_memory(summary).unwrap();
+        let report = scan_img_with_session(summary, &ScanOptions::default(), &mut session);
+        assert!(report.unwrap().contains("\nTraded, gains boosted EXP.\n"));
+
+        for _ in 0..2 {
+            let report = scan(draw_gsc_badge_page(&chars, 8, &[0, 1]), &mut session);
+            assert_eq!(report, Ok("Johto badges: Zephyr, Hive (2/8)\n".to_string()));
+        }
+        let report = scan(draw_gsc_badge_page(&chars, 16, &[0]), &mut session);
+        assert_eq!(report, Ok("Kanto badges: Boulder (1/8)\n".to_string()));
+        assert_eq!(session.badges(), ["Zephyr", "Hive", "Boulder"]);
+    }
+
+    /// Draws the RBY bag with a TM and a key item.
+    fn draw_rby_bag(chars: &CharTable) -> GrayImage {
+        let mut img = GrayImage::new(160, 144);
+        draw_text(&mut img, "POTION", 48, 32, chars);
+        draw_text(&mut img, "12", 120, 40, chars);
+        draw_text(&mut img, "TM24", 48, 48, chars);
+        draw_text(&mut img, " 1", 120, 56, chars);
+        draw_text(&mut img, "BICYCLE", 48, 64, chars);
+        draw_text(&mut img, "CANCEL", 48, 80, chars);
+        draw_cursor(&mut img, 40, 48);
+        img
+    }
+
+    #[test]
+    fn read_bags() {
+        let chars = CharTable::new();
+        let img = draw_rby_bag(&chars);
+
+        let layout = RbyBag::new();
+        assert!(layout.verify_layout(&img, &chars));
+        let content = layout.read_fields(&img, &chars).unwrap();
+        assert_eq!(
+            content.items,
+            [
+                BagItem {
+                    name: "POTION".to_string(),
+                    quantity: Some(12),
+                },
+                BagItem {
+                    name: "TM24".to_string(),
+                    quantity: Some(1),
+                },
+                BagItem {
+                    name: "BICYCLE".to_string(),
+                    quantity: None,
+                },
+            ]
+        );
+        assert_eq!((content.cursor, content.is_end), (1, true));
+
+        let mut img = GrayImage::new(160, 144);
+        draw_text(&mut img, "LEFTOVERS", 40, 16, &chars);
+        draw_text(&mut img, " 1", 128, 24, &chars);
+        draw_cursor(&mut img, 32, 16);
+
+        let layout = GscBag::new();
+        assert!(layout.verify_layout(&img, &chars));
+        let content = layout.read_fields(&img, &chars).unwrap();
+        assert_eq!(content.items[0].quantity, Some(1));
+        assert!(!content.is_end);
+    }
+    #[test]
+    fn scan_bag_report() {
+        let mut img = draw_rby_bag(&CharTable::new());
+        invert(&mut img);
+
+        let img = DynamicImage::ImageLuma8(img);
+        let report = scan_bag_with(img.clone(), &native_screen(), &["Pikachu"]).unwrap();
+        assert_eq!(
+            report,
+            "POTION ×12\nTM24 ×1  Thunderbolt\n  Unknown for: Pikachu (no TM/HM learnset data)\nBICYCLE\nEnd of list\n"
+        );
+
+        // The party is taken from the party menu scanned before
+        let mut session = ScanSession::new();
+        let report = scan_bag_with_session(img.clone(), &native_screen(), &session).unwrap();
+        assert!(
+            report.ends_with("\nScan the party menu to see which members learn the TMs and HMs.\n")
+        );
+
+        let mut party = draw_rby_party(&CharTable::new());
+        invert(&mut party);
+        let party = DynamicImage::ImageLuma8(party);
+        scan_img_with_session(party, &native_screen(), &mut session).unwrap();
+        assert_eq!(session.party(), ["Pikachu", "Pidgey"]);
+
+        let report = scan_bag_with_session(img, &native_screen(), &session).unwrap();
+        assert!(report.contains("  Unknown for: Pikachu, Pidgey (no TM/HM learnset data)\n"));
+    }
 }
@@ -90,6 +90,126 @@ impl GscItems {
 
         item
     }
+
+    /// Returns the names the items can be looked up with, in lower case.
+    pub fn names(&self) -> Vec<&str> {
+        self.data
+            .keys()
+            .chain(self.modified_names.keys())
+            .map(|name| name.as_str())
+            .collect()
+    }
+}
+
+/// The items of RBY with a description.
+///
+/// Only the balls and the evolution stones are listed, as the other items
+/// are not used in competitive play.
+pub struct RbyItems {
+    data: HashMap<String, Item>,
+    modified_names: HashMap<String, String>,
+}
+
+impl RbyItems {
+    pub fn new() -> RbyItems {
+        let mut items = HashMap::new();
+
+        const CSV_DATA: &str = include_str!("../data/smogon_rb_items.csv");
+        let mut csv_reader = csv::ReaderBuilder::new()
+            .has_headers(true)
+            .from_reader(CSV_DATA.as_bytes());
+
+        for result in csv_reader.deserialize() {
+            let item: Item = result.expect("could not deserialize Item");
+            items.insert(item.name.to_lowercase(), item);
+        }
+
+        let modified_names = HashMap::from(
+            [("thunderstone", "thunder stone")].map(|(k, v)| (k.to_string(), v.to_string())),
+        );
+
+        RbyItems {
+            data: items,
+            modified_names,
+        }
+    }
+
+    /// Returns a reference to the Item corresponding to the name.
+    pub fn get(&self, name: &str) -> Option<&Item> {
+        let name = name.to_lowercase();
+        let name = self.modified_names.get(&name).unwrap_or(&name);
+        self.data.get(name)
+    }
+
+    /// Returns the names the items can be looked up with, in lower case.
+    pub fn names(&self) -> Vec<&str> {
+        self.data
+            .keys()
+            .chain(self.modified_names.keys())
+            .map(|name| name.as_str())
+            .collect()
+    }
+}
+
+impl Default for RbyItems {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct TmHmRecord {
+    item: String,
+    /// Empty for the HMs missing from RBY.
+    rby: Option<String>,
+    gsc: String,
+}
+
+/// The moves taught by the TMs and HMs of a generation.
+pub struct TmHms {
+    moves: Vec<(String, String)>,
+}
+
+impl TmHms {
+    /// Creates the TMs and HMs of RBY.
+    pub fn rby() -> TmHms {
+        TmHms::from_records(|record| record.rby.clone())
+    }
+
+    /// Creates the TMs and HMs of GSC.
+    pub fn gsc() -> TmHms {
+        TmHms::from_records(|record| Some(record.gsc.clone()))
+    }
+
+    /// Creates the TMs and HMs from the moves picked from the records.
+    fn from_records(move_name: impl Fn(&TmHmRecord) -> Option<String>) -> TmHms {
+        const CSV_DATA: &str = include_str!("../data/tm_hm.csv");
+        let mut csv_reader = csv::ReaderBuilder::new()
+            .has_headers(true)
+            .from_reader(CSV_DATA.as_bytes());
+
+        let mut moves = Vec::new();
+        for result in csv_reader.deserialize() {
+            let record: TmHmRecord = result.expect("could not deserialize TM or HM");
+            if let Some(move_name) = move_name(&record) {
+                moves.push((record.item, move_name));
+            }
+        }
+
+        TmHms { moves }
+    }
+
+    /// Returns the name of the move taught by the TM or HM, e.g. "TM01".
+    ///
+    /// The name may be followed by other text, like the move in the GSC
+    /// TM pocket.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        let item = name.get(..4)?;
+        self.moves
+            .iter()
+            .find(|(x, _)| x == item)
+            .map(|(_, move_name)| move_name.as_str())
+    }
 }
 
 #[cfg(test)]
@@ -119,4 +239,24 @@ mod tests {
 
         assert!(i.is_some_and(|i| i.description == "Holder's Ice-type attacks have 1.1x power."));
     }
+    #[test]
+    fn get_rby_item() {
+        let items = RbyItems::new();
+        assert!(items.get("MOON STONE").is_some());
+        assert!(items.get("THUNDERSTONE").is_some());
+        assert!(items.get("POTION").is_none());
+    }
+
+    #[test]
+    fn moves_of_tms_and_hms() {
+        let rby = TmHms::rby();
+        let gsc = TmHms::gsc();
+
+        assert_eq!(rby.get("TM24"), Some("Thunderbolt"));
+        assert_eq!(gsc.get("TM24"), Some("Dragon Breath"));
+        assert_eq!(gsc.get("TM01 DYNAMICPUNCH"), Some("Dynamic Punch"));
+        assert_eq!(rby.get("HM07"), None);
+        assert_eq!(gsc.get("HM07"), Some("Waterfall"));
+        assert_eq!(gsc.get("POTION"), None);
+    }
 }
@@ -6,6 +6,101 @@ pub struct Learnset {
     pub ndex: String,
     pub pokemon: String,
     pub by_leveling_up: Vec<Vec<String>>,
+    /// The TMs and HMs with the moves they teach, None if not collected.
+    #[serde(default)]
+    pub by_tm_hm: Option<Vec<Vec<String>>>,
+}
+
+/// A move learned by leveling up.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LevelUpMove {
+    /// The game versions, e.g. "RGB", None if shared by all versions.
+    pub version: Option<String>,
+    pub level: i32,
+    pub name: String,
+}
+
+impl Learnset {
+    /// Returns the moves learned at the lowest level above the given one.
+    ///
+    /// The learnsets differing among the game versions have a level column
+    /// per version, the moves are returned for each of them.
+    pub fn next_moves(&self, level: i32) -> Vec<LevelUpMove> {
+        let Some((header, rows)) = self.by_leveling_up.split_first() else {
+            return Vec::new();
+        };
+
+        let mut next_moves = Vec::new();
+        for (col, version) in header.iter().enumerate().take(header.len() - 1) {
+            let learned_above: Vec<(i32, &String)> = rows
+                .iter()
+                .filter_map(|row| {
+                    let move_level = row.get(col)?.parse::<i32>().ok()?;
+                    Some((move_level, row.last()?))
+                })
+                .filter(|(move_level, _)| *move_level > level)
+                .collect();
+
+            let Some(next_level) = learned_above.iter().map(|(x, _)| *x).min() else {
+                continue;
+            };
+            for (move_level, name) in learned_above {
+                if move_level == next_level {
+                    next_moves.push(LevelUpMove {
+                        version: Some(version.clone()).filter(|_| header.len() > 2),
+                        level: move_level,
+                        name: name.clone(),
+                    });
+                }
+            }
+        }
+        next_moves
+    }
+
+    /// Returns true if the Pokémon learns the move of the TM or HM, e.g. "TM01".
+    ///
+    /// Returns None if the learnset lacks the TMs and HMs.
+    pub fn learns_tm_hm(&self, item: &str) -> Option<bool> {
+        let by_tm_hm = self.by_tm_hm.as_ref()?;
+        Some(
+            by_tm_hm
+                .iter()
+                .skip(1)
+                .any(|row| row.first().is_some_and(|x| x == item)),
+        )
+    }
+
+    /// Returns the last four moves learned up to the given level.
+    ///
+    /// These are the moves of a wild Pokémon, and of the Pokémon of most
+    /// trainers. Like [`Learnset::next_moves`], the moves are returned for
+    /// each game version.
+    pub fn last_moves(&self, level: i32) -> Vec<LevelUpMove> {
+        let Some((header, rows)) = self.by_leveling_up.split_first() else {
+            return Vec::new();
+        };
+
+        let mut last_moves = Vec::new();
+        for (col, version) in header.iter().enumerate().take(header.len() - 1) {
+            let mut learned: Vec<LevelUpMove> = rows
+                .iter()
+                .filter_map(|row| {
+                    let move_level = row.get(col)?.parse::<i32>().ok()?;
+                    Some(LevelUpMove {
+                        version: Some(version.clone()).filter(|_| header.len() > 2),
+                        level: move_level,
+                        name: row.last()?.clone(),
+                    })
+                })
+                .filter(|move_| move_.level <= level)
+                .collect();
+            learned.sort_by_key(|move_| move_.level);
+
+            let forgotten = learned.len().saturating_sub(4);
+            last_moves.extend(learned.into_iter().skip(forgotten));
+        }
+        last_moves
+    }
 }
 
 /// Contains the learnsets for the 151 pokemon in RBY.
@@ -67,3 +162,69 @@ impl GscLearnsets {
         learnset
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_moves_per_version() {
+        let learnsets = RbyLearnsets::new();
+
+        let next_moves = learnsets.get_pokemon("Pikachu").unwrap().next_moves(5);
+        let next_moves: Vec<_> = next_moves
+            .iter()
+            .map(|x| (x.version.as_deref(), x.level, x.name.as_str()))
+            .collect();
+        assert_eq!(
+            next_moves,
+            [
+                (Some("RGB"), 9, "Thunder Wave"),
+                (Some("Y"), 6, "Tail Whip")
+            ]
+        );
+
+        let next_moves = learnsets.get_pokemon("Squirtle").unwrap().next_moves(5);
+        assert_eq!(next_moves[0].version, None);
+        assert_eq!(next_moves[0].name, "Bubble");
+        assert!(learnsets
+            .get_pokemon("Mew")
+            .unwrap()
+            .next_moves(100)
+            .is_empty());
+    }
+
+    #[test]
+    fn last_moves_of_wild_pokemon() {
+        let learnsets = RbyLearnsets::new();
+        let pidgey = learnsets.get_pokemon("Pidgey").unwrap();
+
+        let names = |level| -> Vec<String> {
+            pidgey
+                .last_moves(level)
+                .into_iter()
+                .map(|x| x.name)
+                .collect()
+        };
+        assert_eq!(names(5), ["Gust", "Sand-Attack"]);
+        assert_eq!(
+            names(28),
+            ["Sand-Attack", "Quick Attack", "Whirlwind", "Wing Attack"]
+        );
+    }
+    #[test]
+    fn learns_tm_hm_if_collected() {
+        let mut learnset: Learnset = serde_json::from_str(
+            r#"{"ndex": "25", "pokemon": "Pikachu", "by_leveling_up": [["Level", "Move"]]}"#,
+        )
+        .unwrap();
+        assert_eq!(learnset.learns_tm_hm("TM24"), None);
+
+        learnset.by_tm_hm = Some(vec![
+            vec!["TM/HM".to_string(), "Move".to_string()],
+            vec!["TM24".to_string(), "Thunderbolt".to_string()],
+        ]);
+        assert_eq!(learnset.learns_tm_hm("TM24"), Some(true));
+        assert_eq!(learnset.learns_tm_hm("HM03"), Some(false));
+    }
+}
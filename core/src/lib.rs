@@ -1,3 +1,6 @@
+pub mod calibration;
+pub mod correction;
+pub mod dialog;
 pub mod evos;
 pub mod fmt;
 pub mod gameboy;
@@ -5,8 +8,15 @@ pub mod items;
 pub mod learnset;
 pub mod moves;
 pub mod ocr;
+pub mod palette;
+pub mod pc;
 pub mod pokemon;
 pub mod position;
 pub mod roi;
+pub mod screen;
+pub mod session;
 pub mod stats;
+pub mod text;
+pub mod tilemap;
+pub mod types;
 pub mod utils;
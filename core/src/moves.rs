@@ -20,6 +20,25 @@ pub struct Move {
     pub description: String,
 }
 
+impl Move {
+    /// Returns the number of PP Ups used on the move, from its max PP.
+    ///
+    /// A PP Up raises the max PP by a fifth of the base PP, by 7 at most.
+    pub fn pp_ups(&self, max_pp: i32) -> Result<i32, String> {
+        let base_pp = self
+            .pp
+            .parse::<i32>()
+            .map_err(|_| format!("could not parse PP '{}' of {} to i32", self.pp, self.name))?;
+        let pp_up = (base_pp / 5).min(7);
+        (0..=3)
+            .find(|pp_ups| base_pp + pp_ups * pp_up == max_pp)
+            .ok_or(format!(
+                "max PP {max_pp} does not match base PP {base_pp} of {}",
+                self.name
+            ))
+    }
+}
+
 /// The moves available in Gen I.
 ///
 /// Note:
@@ -94,6 +113,15 @@ impl Moves {
 
         move_
     }
+
+    /// Returns the names the moves can be looked up with, in lower case.
+    pub fn names(&self) -> Vec<&str> {
+        self.data
+            .keys()
+            .chain(self.modified_names.keys())
+            .map(|name| name.as_str())
+            .collect()
+    }
 }
 
 pub struct GscMoves {
@@ -165,6 +193,15 @@ impl GscMoves {
 
         move_
     }
+
+    /// Returns the names the moves can be looked up with, in lower case.
+    pub fn names(&self) -> Vec<&str> {
+        self.data
+            .keys()
+            .chain(self.modified_names.keys())
+            .map(|name| name.as_str())
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -194,4 +231,17 @@ mod tests {
 
         assert!(move_.is_some_and(|m| m.type_ == "Poison"));
     }
+
+    #[test]
+    fn pp_ups_from_max_pp() {
+        let moves = Moves::new();
+        let thundershock = moves.get("ThunderShock").unwrap();
+        assert_eq!(thundershock.pp_ups(30), Ok(0));
+        assert_eq!(thundershock.pp_ups(36), Ok(1));
+        assert!(thundershock.pp_ups(35).is_err());
+
+        // Raised by 7 instead of 8
+        let growl = moves.get("Growl").unwrap();
+        assert_eq!(growl.pp_ups(61), Ok(3));
+    }
 }
@@ -7,10 +7,11 @@
 //! https://bulbapedia.bulbagarden.net/wiki/Text_entry_in_the_Pok%C3%A9mon_games  
 //! https://bulbapedia.bulbagarden.net/wiki/Text_entry_(Generation_II)
 
+use crate::palette::normalize_palette;
 use crate::position::Position;
 use crate::roi::Roi;
 use image::imageops::invert;
-use image::GrayImage;
+use image::{DynamicImage, GrayImage};
 use imageproc::contrast::threshold_mut;
 use std::collections::HashMap;
 use std::ops::Deref;
@@ -21,7 +22,7 @@ use std::ops::Deref;
 /// as a u64 value, where the n-th bit correponds to the n-th pixel in
 /// row-major order. The bits of the background have a value of 0.
 /// Pixels with a non-zero value are part of the foreground.
-#[derive(PartialEq, Eq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct CharBitmap(u64);
 
 impl CharBitmap {
@@ -70,71 +71,281 @@ impl CharBitmap {
     }
 }
 
+/// Describes where the characters are on a glyph sheet.
+///
+/// A glyph sheet is an image with characters on a grid, such as a screenshot
+/// of the nicknaming screen. The sheet is stored as JSON next to the image:
+///
+/// ```json
+/// {"x": 16, "y": 64, "step_x": 16, "step_y": 16, "rows": [["A", "B", "C"]]}
+/// ```
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct GlyphSheet {
+    /// The x coordinate of the top-left character.
+    pub x: u32,
+    /// The y coordinate of the top-left character.
+    pub y: u32,
+    /// The horizontal distance between the characters.
+    pub step_x: u32,
+    /// The vertical distance between the characters.
+    pub step_y: u32,
+    /// The characters row by row. Empty strings mark the cells to skip.
+    pub rows: Vec<Vec<String>>,
+}
+
+impl GlyphSheet {
+    /// Deserializes the glyph sheet from JSON.
+    pub fn from_json(text: &str) -> Result<GlyphSheet, String> {
+        serde_json::from_str(text).map_err(|err| format!("could not parse glyph sheet: {err}"))
+    }
+}
+
+/// A character of the font file.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct FontGlyph {
+    char: String,
+    /// The 7 rows of pixels, "#" for the foreground and "." for the background.
+    pixels: Vec<String>,
+}
+
+/// The font file, containing every character of a [`CharTable`].
+#[derive(serde::Serialize, serde::Deserialize)]
+struct FontFile {
+    glyphs: Vec<FontGlyph>,
+}
+
+/// Configures how closely a character has to resemble its glyph.
+///
+/// Compression and scaling can flip a few pixels of a character. These are
+/// still recognized, as long as the closest glyph is unambiguous.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct MatchOptions {
+    /// The maximal number of pixels a character may differ from its glyph.
+    pub max_dist: u32,
+    /// The minimal difference between the distances of the closest glyph and
+    /// the closest glyph of a different character.
+    pub margin: u32,
+}
+
+impl Default for MatchOptions {
+    fn default() -> Self {
+        MatchOptions {
+            max_dist: 2,
+            margin: 2,
+        }
+    }
+}
+
+/// A recognized character.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CharMatch {
+    pub char: String,
+    /// The number of pixels the character differs from its glyph.
+    pub dist: u32,
+    /// The confidence of the recognition, between 0 and 1.
+    pub confidence: f32,
+}
+
+/// A recognized line of text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldMatch {
+    pub text: String,
+    pub chars: Vec<CharMatch>,
+}
+
+impl FieldMatch {
+    /// Returns the lowest confidence among the characters.
+    pub fn confidence(&self) -> f32 {
+        self.chars.iter().map(|c| c.confidence).fold(1.0, f32::min)
+    }
+
+    /// Returns the indices of the characters that did not match their glyph exactly.
+    pub fn uncertain(&self) -> Vec<usize> {
+        self.chars
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| c.dist > 0)
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+}
+
 /// Enables to decode a character on the screen.
 ///
-/// Contains upper case letters and digits for Pokemon RBY and GSC.
-/// No other character was necessery yet.
+/// Contains the characters of the nicknaming screens of Pokemon RBY and GSC:
+/// upper and lower case letters, digits, punctuation, symbols and the "PK" and
+/// "MN" ligatures, as well as the apostrophe of GSC. The digits, the "'d",
+/// "'l", "'s" and "é" ligatures and the shiny star of GSC do not appear on
+/// these screens and are given pixel by pixel.
 pub struct CharTable {
-    chars: HashMap<CharBitmap, &'static str>,
+    chars: HashMap<CharBitmap, String>,
 }
 
 const IMG_NICKNAMING_1: &[u8] = include_bytes!("../data/images/Yellow_nicknaming_upper.png");
 const IMG_NICKNAMING_2: &[u8] = include_bytes!("../data/images/Crystal_nicknaming_upper.png");
+const IMG_NICKNAMING_3: &[u8] = include_bytes!("../data/images/Yellow_nicknaming_lower.png");
+const IMG_NICKNAMING_4: &[u8] = include_bytes!("../data/images/Crystal_nicknaming_lower.png");
+
+/// The characters of the RBY upper case nicknaming screen by row and column.
+const RBY_UPPER_CASE_POSITIONS: [(&str, u32, u32); 34] = [
+    ("A", 0, 0),
+    ("B", 0, 1),
+    ("C", 0, 2),
+    ("D", 0, 3),
+    ("E", 0, 4),
+    ("F", 0, 5),
+    ("G", 0, 6),
+    ("H", 0, 7),
+    ("I", 0, 8),
+    ("J", 1, 0),
+    ("K", 1, 1),
+    ("L", 1, 2),
+    ("M", 1, 3),
+    ("N", 1, 4),
+    ("O", 1, 5),
+    ("P", 1, 6),
+    ("Q", 1, 7),
+    ("R", 1, 8),
+    ("S", 2, 0),
+    ("T", 2, 1),
+    ("U", 2, 2),
+    ("V", 2, 3),
+    ("W", 2, 4),
+    ("X", 2, 5),
+    ("Y", 2, 6),
+    ("Z", 2, 7),
+    (" ", 2, 8),
+    ("-", 4, 0),
+    ("?", 4, 1),
+    ("!", 4, 2),
+    ("♂", 4, 3),
+    ("♀", 4, 4),
+    (".", 4, 6),
+    (",", 4, 7),
+];
+
+/// The characters of the GSC upper case nicknaming screen by row and column.
+const GSC_UPPER_CASE_POSITIONS: [(&str, u32, u32); 32] = [
+    ("A", 0, 0),
+    ("B", 0, 1),
+    ("C", 0, 2),
+    ("D", 0, 3),
+    ("E", 0, 4),
+    ("F", 0, 5),
+    ("G", 0, 6),
+    ("H", 0, 7),
+    ("I", 0, 8),
+    ("J", 1, 0),
+    ("K", 1, 1),
+    ("L", 1, 2),
+    ("M", 1, 3),
+    ("N", 1, 4),
+    ("O", 1, 5),
+    ("P", 1, 6),
+    ("Q", 1, 7),
+    ("R", 1, 8),
+    ("S", 2, 0),
+    ("T", 2, 1),
+    ("U", 2, 2),
+    ("V", 2, 3),
+    ("W", 2, 4),
+    ("X", 2, 5),
+    ("Y", 2, 6),
+    ("Z", 2, 7),
+    (" ", 2, 8),
+    ("-", 3, 0),
+    ("?", 3, 1),
+    ("!", 3, 2),
+    (".", 3, 4),
+    (",", 3, 5),
+];
+
+/// The characters of the lower case nicknaming screens by row and column.
+const LOWER_CASE_POSITIONS: [(&str, u32, u32); 35] = [
+    ("a", 0, 0),
+    ("b", 0, 1),
+    ("c", 0, 2),
+    ("d", 0, 3),
+    ("e", 0, 4),
+    ("f", 0, 5),
+    ("g", 0, 6),
+    ("h", 0, 7),
+    ("i", 0, 8),
+    ("j", 1, 0),
+    ("k", 1, 1),
+    ("l", 1, 2),
+    ("m", 1, 3),
+    ("n", 1, 4),
+    ("o", 1, 5),
+    ("p", 1, 6),
+    ("q", 1, 7),
+    ("r", 1, 8),
+    ("s", 2, 0),
+    ("t", 2, 1),
+    ("u", 2, 2),
+    ("v", 2, 3),
+    ("w", 2, 4),
+    ("x", 2, 5),
+    ("y", 2, 6),
+    ("z", 2, 7),
+    ("×", 3, 0),
+    ("(", 3, 1),
+    (")", 3, 2),
+    (":", 3, 3),
+    (";", 3, 4),
+    ("[", 3, 5),
+    ("]", 3, 6),
+    ("PK", 3, 7),
+    ("MN", 3, 8),
+];
+
+/// The lower case letters that reach below the baseline.
+///
+/// They are drawn across all 8 rows of their tile. As their first row is
+/// empty, they are encoded a pixel lower; their last row tells "g" and "q" apart.
+const DESCENDERS: [&str; 5] = ["g", "j", "p", "q", "y"];
+
+/// Loads the screenshot of a nicknaming screen as a binary image.
+fn load_nicknaming(bytes: &[u8]) -> GrayImage {
+    let img_nicknaming = image::load_from_memory(bytes).expect("failed to load image");
+    let mut img_nicknaming = img_nicknaming.to_luma8();
+    threshold_mut(&mut img_nicknaming, 200); // Needed as black is 7 white is 23x
+    invert(&mut img_nicknaming); // Background should have the value of 0
+    img_nicknaming
+}
+
+/// Returns the position of a character on the keyboard of the nicknaming screen.
+///
+/// The first character is at (16, `offset_y`), the characters are 16 pixels apart.
+fn keyboard_position(char: &str, row: u32, col: u32, offset_y: u32) -> Position {
+    let descent = DESCENDERS.contains(&char) as u32;
+    Position {
+        x: 16 + col * 16,
+        y: offset_y + row * 16 + descent,
+        width: 7,
+        height: 7,
+    }
+}
 
 impl CharTable {
     pub fn new() -> CharTable {
         let mut chars = HashMap::<CharBitmap, &str>::new();
 
-        let img_nicknaming =
-            image::load_from_memory(IMG_NICKNAMING_1).expect("failed to load image");
-        let mut img_nicknaming = img_nicknaming.to_luma8();
-        threshold_mut(&mut img_nicknaming, 200); // Needed as black is 7 white is 23x
-        invert(&mut img_nicknaming); // Background should have the value of 0
-        let img_nicknaming = img_nicknaming;
-
-        let char_positions = [
-            ("A", 0, 0),
-            ("B", 0, 1),
-            ("C", 0, 2),
-            ("D", 0, 3),
-            ("E", 0, 4),
-            ("F", 0, 5),
-            ("G", 0, 6),
-            ("H", 0, 7),
-            ("I", 0, 8),
-            ("J", 1, 0),
-            ("K", 1, 1),
-            ("L", 1, 2),
-            ("M", 1, 3),
-            ("N", 1, 4),
-            ("O", 1, 5),
-            ("P", 1, 6),
-            ("Q", 1, 7),
-            ("R", 1, 8),
-            ("S", 2, 0),
-            ("T", 2, 1),
-            ("U", 2, 2),
-            ("V", 2, 3),
-            ("W", 2, 4),
-            ("X", 2, 5),
-            ("Y", 2, 6),
-            ("Z", 2, 7),
-            (" ", 2, 8),
-            ("-", 4, 0),
-        ];
-        for (char, row, col) in char_positions {
-            let char_pos = Position {
-                x: 16 + col * 16,
-                y: 40 + row * 16,
-                width: 7,
-                height: 7,
-            };
-
+        let img_nicknaming = load_nicknaming(IMG_NICKNAMING_1);
+        for (char, row, col) in RBY_UPPER_CASE_POSITIONS {
+            let char_pos = keyboard_position(char, row, col, 40);
             let bitmap = CharBitmap::from_image_region(&img_nicknaming, &char_pos).unwrap();
 
             chars.insert(bitmap, char);
         }
 
+        let img_nicknaming = load_nicknaming(IMG_NICKNAMING_3);
+        for (char, row, col) in LOWER_CASE_POSITIONS {
+            let char_pos = keyboard_position(char, row, col, 40);
+            let bitmap = CharBitmap::from_image_region(&img_nicknaming, &char_pos).unwrap();
+            chars.insert(bitmap, char);
+        }
+
         let char = "0";
         let code = CharBitmap::from_pixels(&[
             0, 0, 0, 0, 0, 0, 0, //
@@ -291,57 +502,101 @@ impl CharTable {
         .unwrap();
         chars.insert(code, char);
 
-        // GSC has a slightly modified set of character
+        // The ligatures of the apostrophe, e.g. "I'd", "we'll" and "RED's"
+        let char = "'d";
+        let code = CharBitmap::from_pixels(&[
+            1, 1, 0, 0, 0, 0, 1, //
+            0, 1, 0, 0, 0, 0, 1, //
+            1, 0, 0, 0, 0, 0, 1, //
+            0, 0, 0, 1, 1, 1, 1, //
+            0, 0, 1, 0, 0, 0, 1, //
+            0, 0, 1, 0, 0, 0, 1, //
+            0, 0, 0, 1, 1, 1, 1, //
+        ])
+        .unwrap();
+        chars.insert(code, char);
 
-        let img_nicknaming =
-            image::load_from_memory(IMG_NICKNAMING_2).expect("failed to load image");
-        let mut img_nicknaming = img_nicknaming.to_luma8();
-        threshold_mut(&mut img_nicknaming, 200); // Needed as black is 7 white is 23x
-        invert(&mut img_nicknaming);
-        let img_nicknaming = img_nicknaming;
-
-        let char_positions = [
-            ("A", 0, 0),
-            ("B", 0, 1),
-            ("C", 0, 2),
-            ("D", 0, 3),
-            ("E", 0, 4),
-            ("F", 0, 5),
-            ("G", 0, 6),
-            ("H", 0, 7),
-            ("I", 0, 8),
-            ("J", 1, 0),
-            ("K", 1, 1),
-            ("L", 1, 2),
-            ("M", 1, 3),
-            ("N", 1, 4),
-            ("O", 1, 5),
-            ("P", 1, 6),
-            ("Q", 1, 7),
-            ("R", 1, 8),
-            ("S", 2, 0),
-            ("T", 2, 1),
-            ("U", 2, 2),
-            ("V", 2, 3),
-            ("W", 2, 4),
-            ("X", 2, 5),
-            ("Y", 2, 6),
-            ("Z", 2, 7),
-            (" ", 2, 8),
-        ];
-        for (char, row, col) in char_positions {
-            let char_pos = Position {
-                x: 16 + col * 16,
-                y: 64 + row * 16,
-                width: 7,
-                height: 7,
-            };
+        let char = "'l";
+        let code = CharBitmap::from_pixels(&[
+            1, 1, 0, 0, 1, 0, 0, //
+            0, 1, 0, 0, 1, 0, 0, //
+            1, 0, 0, 0, 1, 0, 0, //
+            0, 0, 0, 0, 1, 0, 0, //
+            0, 0, 0, 0, 1, 0, 0, //
+            0, 0, 0, 0, 1, 0, 0, //
+            0, 0, 0, 0, 1, 0, 0, //
+        ])
+        .unwrap();
+        chars.insert(code, char);
 
+        let char = "'s";
+        let code = CharBitmap::from_pixels(&[
+            1, 1, 0, 0, 0, 0, 0, //
+            0, 1, 0, 0, 0, 0, 0, //
+            1, 0, 0, 1, 1, 1, 1, //
+            0, 0, 1, 0, 0, 0, 0, //
+            0, 0, 0, 1, 1, 1, 0, //
+            0, 0, 0, 0, 0, 0, 1, //
+            0, 0, 1, 1, 1, 1, 0, //
+        ])
+        .unwrap();
+        chars.insert(code, char);
+
+        // The "é" of "POKéMON"
+        let char = "é";
+        let code = CharBitmap::from_pixels(&[
+            0, 0, 0, 0, 1, 0, 0, //
+            0, 0, 0, 1, 0, 0, 0, //
+            0, 0, 1, 1, 1, 1, 0, //
+            0, 1, 0, 0, 0, 0, 1, //
+            0, 1, 1, 1, 1, 1, 1, //
+            0, 1, 0, 0, 0, 0, 0, //
+            0, 0, 1, 1, 1, 1, 1, //
+        ])
+        .unwrap();
+        chars.insert(code, char);
+
+        // GSC has a slightly modified set of character
+
+        let img_nicknaming = load_nicknaming(IMG_NICKNAMING_2);
+        for (char, row, col) in GSC_UPPER_CASE_POSITIONS {
+            let char_pos = keyboard_position(char, row, col, 64);
             let bitmap = CharBitmap::from_image_region(&img_nicknaming, &char_pos).unwrap();
 
             chars.insert(bitmap, char);
         }
 
+        // The apostrophe of the "TOTODILE'S NICKNAME?" title
+        let char_pos = Position {
+            x: 104,
+            y: 16,
+            width: 7,
+            height: 7,
+        };
+        let bitmap = CharBitmap::from_image_region(&img_nicknaming, &char_pos).unwrap();
+        chars.insert(bitmap, "'");
+
+        let img_nicknaming = load_nicknaming(IMG_NICKNAMING_4);
+        for (char, row, col) in LOWER_CASE_POSITIONS {
+            let char_pos = keyboard_position(char, row, col, 64);
+            let bitmap = CharBitmap::from_image_region(&img_nicknaming, &char_pos).unwrap();
+            chars.insert(bitmap, char);
+        }
+
+        // The star shown on the summary of shiny Pokémon
+        let char = "★";
+        let code = CharBitmap::from_pixels(&[
+            0, 0, 0, 1, 0, 0, 0, //
+            0, 0, 1, 1, 1, 0, 0, //
+            1, 1, 1, 1, 1, 1, 1, //
+            0, 1, 1, 1, 1, 1, 0, //
+            0, 0, 1, 1, 1, 0, 0, //
+            0, 1, 1, 0, 1, 1, 0, //
+            1, 1, 0, 0, 0, 1, 1, //
+        ])
+        .unwrap();
+        chars.insert(code, char);
+
         let char = "2";
         let code = CharBitmap::from_pixels(&[
             0, 0, 0, 0, 0, 0, 0, //
@@ -368,12 +623,140 @@ impl CharTable {
         .unwrap();
         chars.insert(code, char);
 
-        CharTable { chars: chars }
+        let chars = chars
+            .into_iter()
+            .map(|(bitmap, char)| (bitmap, char.to_string()))
+            .collect();
+        CharTable { chars }
+    }
+
+    /// Returns the character whose glyph is the closest to the bitmap.
+    pub fn nearest(
+        &self,
+        bitmap: &CharBitmap,
+        options: &MatchOptions,
+    ) -> Result<CharMatch, String> {
+        if let Some(char) = self.chars.get(bitmap) {
+            return Ok(CharMatch {
+                char: char.clone(),
+                dist: 0,
+                confidence: 1.0,
+            });
+        }
+
+        // The closest glyph of each character
+        let mut dists = HashMap::<&str, u32>::new();
+        for (glyph, char) in &self.chars {
+            let dist = glyph.hamming_dist(bitmap);
+            let min_dist = dists.entry(char).or_insert(dist);
+            *min_dist = (*min_dist).min(dist);
+        }
+        let mut dists: Vec<(&str, u32)> = dists.into_iter().collect();
+        dists.sort_by_key(|&(char, dist)| (dist, char));
+
+        let (char, dist) = match dists.first() {
+            Some(&(char, dist)) if dist <= options.max_dist => (char, dist),
+            _ => return Err("could not recognize character".to_string()),
+        };
+        if let Some(&(other, other_dist)) = dists.get(1) {
+            if other_dist - dist < options.margin {
+                return Err(format!("could not tell apart '{char}' and '{other}'"));
+            }
+        }
+
+        Ok(CharMatch {
+            char: char.to_string(),
+            dist,
+            confidence: 1.0 - dist as f32 / (options.max_dist + 1) as f32,
+        })
+    }
+
+    /// Creates a table from the characters of a glyph sheet.
+    pub fn from_glyph_sheet(img: &DynamicImage, sheet: &GlyphSheet) -> Result<CharTable, String> {
+        let img = normalize_palette(img);
+        let mut chars = HashMap::<CharBitmap, String>::new();
+
+        for (row, row_chars) in sheet.rows.iter().enumerate() {
+            for (col, char) in row_chars.iter().enumerate() {
+                if char.is_empty() {
+                    continue;
+                }
+
+                let char_pos = Position {
+                    x: sheet.x + col as u32 * sheet.step_x,
+                    y: sheet.y + row as u32 * sheet.step_y,
+                    width: 7,
+                    height: 7,
+                };
+                let bitmap = CharBitmap::from_image_region(&img, &char_pos)
+                    .map_err(|err| format!("could not read '{char}' of the glyph sheet: {err}"))?;
+
+                chars.insert(bitmap, char.clone());
+            }
+        }
+
+        Ok(CharTable { chars })
+    }
+
+    /// Adds the characters of the other table, replacing the glyphs in common.
+    pub fn extend(&mut self, other: &CharTable) {
+        self.chars
+            .extend(other.chars.iter().map(|(k, v)| (*k, v.clone())));
+    }
+
+    /// Serializes the table into the JSON font file format.
+    pub fn to_json(&self) -> String {
+        let mut glyphs: Vec<(&CharBitmap, &String)> = self.chars.iter().collect();
+        glyphs.sort_by_key(|(bitmap, char)| (char.as_str(), bitmap.0));
+
+        let glyphs = glyphs
+            .into_iter()
+            .map(|(bitmap, char)| FontGlyph {
+                char: char.to_string(),
+                pixels: (0..7)
+                    .map(|row| {
+                        (0..7)
+                            .map(|col| match (bitmap.0 >> (row * 7 + col)) & 1 {
+                                1 => '#',
+                                _ => '.',
+                            })
+                            .collect()
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        serde_json::to_string_pretty(&FontFile { glyphs }).expect("failed to serialize font")
+    }
+
+    /// Deserializes the table from the JSON font file format.
+    pub fn from_json(text: &str) -> Result<CharTable, String> {
+        let font: FontFile =
+            serde_json::from_str(text).map_err(|err| format!("could not parse font: {err}"))?;
+
+        let mut chars = HashMap::<CharBitmap, String>::new();
+        for glyph in font.glyphs {
+            let pixels: Vec<u8> = glyph.pixels.iter().flat_map(|row| row.bytes()).collect();
+            if glyph.pixels.len() != 7 || pixels.iter().any(|x| *x != b'#' && *x != b'.') {
+                return Err(format!(
+                    "expected 7 rows of '#' and '.' for '{}'",
+                    glyph.char
+                ));
+            }
+
+            let pixels: Vec<u8> = pixels.iter().map(|x| (*x == b'#') as u8).collect();
+            let bitmap = CharBitmap::from_pixels(&pixels)
+                .map_err(|err| format!("could not read pixels of '{}': {err}", glyph.char))?;
+
+            chars.insert(bitmap, glyph.char);
+        }
+
+        Ok(CharTable { chars })
     }
 }
 
 impl Deref for CharTable {
-    type Target = HashMap<CharBitmap, &'static str>;
+    type Target = HashMap<CharBitmap, String>;
 
     fn deref(&self) -> &Self::Target {
         &self.chars
@@ -381,11 +764,20 @@ impl Deref for CharTable {
 }
 
 /// Reads a character from a 7x7 pixel large region of an image.
-pub fn read_char(
+///
+/// Tolerates a few differing pixels, see [`MatchOptions::default`].
+pub fn read_char(img: &GrayImage, pos: &Position, chars: &CharTable) -> Result<String, String> {
+    let char = read_char_with(img, pos, chars, &MatchOptions::default())?;
+    Ok(char.char)
+}
+
+/// Reads a character with the given options and returns its confidence as well.
+pub fn read_char_with(
     img: &GrayImage,
     pos: &Position,
     chars: &CharTable,
-) -> Result<&'static str, String> {
+    options: &MatchOptions,
+) -> Result<CharMatch, String> {
     if pos.width != 7 || pos.height != 7 {
         let msg = format!(
             "Expected width and height to be 7, got {:?} and {:?}",
@@ -396,15 +788,28 @@ pub fn read_char(
 
     let bitmap = CharBitmap::from_image_region(img, &pos)?;
 
-    let &char = chars.get(&bitmap).ok_or("could not recognize character")?;
-    Ok(char)
+    chars.nearest(&bitmap, options)
 }
 
 /// Reads one line of text from the image.
 ///
 /// A character is 7 pixels wide and high. There is a single pixel of space
-/// between characters.
+/// between characters. Descenders, like "g", are found a pixel lower.
 pub fn read_field(img: &GrayImage, pos: &Position, chars: &CharTable) -> Result<String, String> {
+    let field = read_field_with(img, pos, chars, &MatchOptions::default())?;
+    Ok(field.text)
+}
+
+/// Reads one line of text with the given options.
+///
+/// Besides the text, returns the confidence of each character, so that the
+/// uncertain ones can be reported.
+pub fn read_field_with(
+    img: &GrayImage,
+    pos: &Position,
+    chars: &CharTable,
+    options: &MatchOptions,
+) -> Result<FieldMatch, String> {
     if pos.height != 7 {
         let msg = format!("Expected height to be 7, got {:?}", pos.height);
         return Err(msg);
@@ -415,7 +820,8 @@ pub fn read_field(img: &GrayImage, pos: &Position, chars: &CharTable) -> Result<
     }
 
     let char_count = (pos.width + 1) / 8;
-    let mut result = String::with_capacity(char_count as usize);
+    let mut text = String::with_capacity(char_count as usize);
+    let mut matches = Vec::with_capacity(char_count as usize);
 
     for i in 0..char_count {
         let offset_x = i * (7 + 1);
@@ -426,11 +832,234 @@ pub fn read_field(img: &GrayImage, pos: &Position, chars: &CharTable) -> Result<
             height: 7,
         };
 
-        let char = read_char(img, &char_pos, chars)
-            .map_err(|err| format!("could not read character #{i}: {err}"))?;
+        let mut char = read_char_with(img, &char_pos, chars, options);
+
+        // Descenders are drawn a pixel lower than the other characters
+        if !matches!(char, Ok(CharMatch { dist: 0, .. })) {
+            let descender_pos = Position {
+                y: char_pos.y + 1,
+                ..char_pos
+            };
+            let descender = read_char_with(img, &descender_pos, chars, options)
+                .ok()
+                .filter(|x| DESCENDERS.contains(&x.char.as_str()));
+            if let Some(descender) = descender {
+                let is_better = match &char {
+                    Ok(char) => descender.dist < char.dist,
+                    Err(_) => true,
+                };
+                if is_better {
+                    char = Ok(descender);
+                }
+            }
+        }
+
+        let char = char.map_err(|err| format!("could not read character #{i}: {err}"))?;
+
+        text.push_str(&char.char);
+        matches.push(char);
+    }
+
+    Ok(FieldMatch {
+        text,
+        chars: matches,
+    })
+}
+
+/// Draws the text on the binary image, starting from the pixel coordinates.
+///
+/// Creates the screens of the tests there are no screenshots of.
+#[cfg(test)]
+pub(crate) fn draw_text(img: &mut GrayImage, text: &str, x: u32, y: u32, chars: &CharTable) {
+    for (idx, char) in text.chars().enumerate() {
+        let char = char.to_string();
+        let glyph = chars
+            .iter()
+            .filter(|(_, x)| **x == char)
+            .map(|(bitmap, _)| bitmap.0)
+            .min() // The games may draw the char differently
+            .expect("could not find char");
+        let descent = DESCENDERS.contains(&char.as_str()) as u32;
+
+        for pixel in 0..49 {
+            if (glyph >> pixel) & 1 == 1 {
+                let pixel_x = x + idx as u32 * 8 + pixel % 7;
+                let pixel_y = y + descent + pixel / 7;
+                img.put_pixel(pixel_x, pixel_y, image::Luma([255]));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::imageops::{crop_imm, replace};
+
+    #[test]
+    fn read_every_glyph() {
+        let chars = CharTable::new();
+        let sheets = [
+            (IMG_NICKNAMING_1, 40, &RBY_UPPER_CASE_POSITIONS[..]),
+            (IMG_NICKNAMING_3, 40, &LOWER_CASE_POSITIONS[..]),
+            (IMG_NICKNAMING_2, 64, &GSC_UPPER_CASE_POSITIONS[..]),
+            (IMG_NICKNAMING_4, 64, &LOWER_CASE_POSITIONS[..]),
+        ];
+
+        for (bytes, offset_y, char_positions) in sheets {
+            let img = load_nicknaming(bytes);
+            for &(char, row, col) in char_positions {
+                let pos = keyboard_position(char, row, col, offset_y);
+                assert_eq!(read_char(&img, &pos, &chars), Ok(char.to_string()));
+            }
+        }
+
+        // The characters given pixel by pixel
+        for char in ["0", "5", "9", "/", "'d", "'l", "'s", "é", "★"] {
+            let glyphs: Vec<&CharBitmap> = chars
+                .iter()
+                .filter(|(_, x)| *x == char)
+                .map(|(bitmap, _)| bitmap)
+                .collect();
+            assert!(!glyphs.is_empty(), "missing '{char}'");
+
+            for glyph in glyphs {
+                let mut img = GrayImage::new(7, 7);
+                for pixel in (0..49).filter(|pixel| (glyph.0 >> pixel) & 1 == 1) {
+                    img.put_pixel(pixel % 7, pixel / 7, image::Luma([255]));
+                }
+                let pos = Position {
+                    x: 0,
+                    y: 0,
+                    width: 7,
+                    height: 7,
+                };
+                assert_eq!(read_char(&img, &pos, &chars), Ok(char.to_string()));
+            }
+        }
+
+        let mut img = GrayImage::new(56, 7);
+        draw_text(&mut img, "POKéMON", 0, 0, &chars);
+        let pos = Position {
+            x: 0,
+            y: 0,
+            width: 55,
+            height: 7,
+        };
+        assert_eq!(read_field(&img, &pos, &chars), Ok("POKéMON".to_string()));
+    }
+
+    #[test]
+    fn read_apostrophe() {
+        let chars = CharTable::new();
+        let img = load_nicknaming(IMG_NICKNAMING_4);
+        let pos = Position {
+            x: 40,
+            y: 16,
+            width: 79,
+            height: 7,
+        };
+
+        let text = read_field(&img, &pos, &chars);
+        assert_eq!(text, Ok("TOTODILE'S".to_string()));
+    }
+
+    #[test]
+    fn read_descender() {
+        let chars = CharTable::new();
+        let keyboard = load_nicknaming(IMG_NICKNAMING_3);
+        let mut img = GrayImage::new(16, 9);
+        replace(
+            &mut img,
+            &crop_imm(&keyboard, 16, 40, 8, 9).to_image(),
+            0,
+            0,
+        ); // "a"
+        replace(
+            &mut img,
+            &crop_imm(&keyboard, 112, 40, 8, 9).to_image(),
+            8,
+            0,
+        ); // "g"
+        let pos = Position {
+            x: 0,
+            y: 0,
+            width: 15,
+            height: 7,
+        };
+
+        assert_eq!(read_field(&img, &pos, &chars), Ok("ag".to_string()));
+    }
+
+    #[test]
+    fn read_noisy_field() {
+        let chars = CharTable::new();
+        let mut img = load_nicknaming(IMG_NICKNAMING_4);
+        let pos = Position {
+            x: 40,
+            y: 16,
+            width: 79,
+            height: 7,
+        };
+        img.put_pixel(42, 16, image::Luma([0])); // Removes a pixel of the first "T"
+
+        let field = read_field_with(&img, &pos, &chars, &MatchOptions::default()).unwrap();
+        assert_eq!(field.text, "TOTODILE'S");
+        assert_eq!(field.uncertain(), vec![0]);
+        assert!(field.confidence() < 1.0);
+        assert_eq!(field.chars[1].confidence, 1.0);
 
-        result.extend(char.chars());
+        let exact = MatchOptions {
+            max_dist: 0,
+            margin: 0,
+        };
+        assert!(read_field_with(&img, &pos, &chars, &exact).is_err());
+    }
+
+    #[test]
+    fn reject_ambiguous_char() {
+        let chars = CharTable::new();
+        let img = load_nicknaming(IMG_NICKNAMING_3);
+        let pos = keyboard_position("i", 0, 8, 40);
+        let options = MatchOptions {
+            max_dist: 2,
+            margin: 3, // "i" and "l" differ in 2 pixels
+        };
+
+        let bitmap = CharBitmap::from_image_region(&img, &pos).unwrap();
+        let bitmap = CharBitmap(bitmap.0 ^ (1 << 48)); // Flips the bottom-right pixel
+        assert!(chars.nearest(&bitmap, &options).is_err());
     }
 
-    Ok(result)
+    #[test]
+    fn load_font_from_glyph_sheet() {
+        let sheet = GlyphSheet::from_json(
+            r#"{
+                "x": 16, "y": 64, "step_x": 16, "step_y": 16,
+                "rows": [
+                    ["A", "B", "C", "D", "E", "F", "G", "H", "I"],
+                    ["J", "K", "L", "M", "N", "O", "P", "Q", "R"],
+                    ["S", "T", "U", "V", "W", "X", "Y", "Z", ""]
+                ]
+            }"#,
+        )
+        .unwrap();
+        let img = image::load_from_memory(IMG_NICKNAMING_2).unwrap();
+
+        let chars = CharTable::from_glyph_sheet(&img, &sheet).unwrap();
+        assert_eq!(chars.len(), 26);
+
+        let text = chars.to_json();
+        let chars = CharTable::from_json(&text).unwrap();
+        assert_eq!(chars.to_json(), text);
+
+        let img = load_nicknaming(IMG_NICKNAMING_2);
+        let pos = Position {
+            x: 40,
+            y: 16,
+            width: 63,
+            height: 7,
+        };
+        assert_eq!(read_field(&img, &pos, &chars), Ok("TOTODILE".to_string()));
+    }
 }
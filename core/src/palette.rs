@@ -0,0 +1,285 @@
+//! Game Boy palette normalization.
+//!
+//! The Game Boy draws every screen with four shades. The DMG shows these as
+//! shades of gray, while the GBC, the SGB and emulators map them to arbitrary
+//! colors, some of which even swap light and dark. A fixed grayscale
+//! threshold cannot separate the text from the background for every palette.
+//!
+//! This module clusters the colors of the screen into its four shades and
+//! maps them to the binary image expected by the layouts: the background has
+//! the value of 0, the text has the value of 255.
+
+use image::{DynamicImage, GrayImage, Luma, Rgb, RgbImage};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// The number of shades the Game Boy shows at once.
+const SHADE_COUNT: usize = 4;
+
+/// The number of k-means iterations used to refine the shades.
+const ITERATION_COUNT: usize = 8;
+
+/// The four shades of a Game Boy screen.
+///
+/// The shades are ordered as they follow each other on the screen, from light
+/// to dark or from dark to light. The last shade is the shade of the text,
+/// the one at the end farther from the background shade.
+#[derive(Debug, Clone)]
+pub struct Palette {
+    pub shades: Vec<Rgb<u8>>,
+    /// The index of the background shade.
+    pub background: usize,
+    /// The index of the first shade that belongs to the foreground.
+    pub foreground: usize,
+}
+
+impl Palette {
+    /// Estimates the palette from the colors of the Game Boy screen.
+    ///
+    /// The colors are clustered with k-means, which also handles the extra
+    /// colors introduced by scaling or compressing the captured screen.
+    /// The most common shade is considered to be the background.
+    pub fn from_image(img: &RgbImage) -> Palette {
+        let mut histogram = HashMap::<[u8; 3], u32>::new();
+        for pixel in img.pixels() {
+            *histogram.entry(pixel.0).or_insert(0) += 1;
+        }
+        let mut colors: Vec<([f32; 3], u32)> = histogram
+            .into_iter()
+            .map(|(c, count)| ([c[0] as f32, c[1] as f32, c[2] as f32], count))
+            .collect();
+        colors.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.partial_cmp(&b.0).unwrap()));
+
+        if colors.is_empty() {
+            return Palette {
+                shades: vec![Rgb([255, 255, 255])],
+                background: 0,
+                foreground: 1,
+            };
+        }
+
+        // Farthest-point initialization, starting from the most common color
+        let mut centers: Vec<[f32; 3]> = vec![colors[0].0];
+        while centers.len() < SHADE_COUNT.min(colors.len()) {
+            let farthest = colors
+                .iter()
+                .map(|(c, _)| {
+                    let dist = centers
+                        .iter()
+                        .map(|x| dist_sq(c, x))
+                        .fold(f32::MAX, f32::min);
+                    (c, dist)
+                })
+                .fold(None, |acc: Option<(&[f32; 3], f32)>, x| match acc {
+                    Some(a) if a.1 >= x.1 => Some(a),
+                    _ => Some(x),
+                });
+            match farthest {
+                Some((c, dist)) if dist > 0.0 => centers.push(*c),
+                _ => break,
+            }
+        }
+
+        let mut counts = vec![0u32; centers.len()];
+        for _ in 0..ITERATION_COUNT {
+            let mut sums = vec![[0.0f32; 3]; centers.len()];
+            counts = vec![0u32; centers.len()];
+            for (color, count) in &colors {
+                let idx = nearest(&centers, color);
+                for ch in 0..3 {
+                    sums[idx][ch] += color[ch] * *count as f32;
+                }
+                counts[idx] += count;
+            }
+            for (idx, center) in centers.iter_mut().enumerate() {
+                if counts[idx] > 0 {
+                    *center = sums[idx].map(|x| x / counts[idx] as f32);
+                }
+            }
+        }
+
+        // Order the shades along the ramp between the two most distant shades
+        let (start, _) = (0..centers.len())
+            .flat_map(|i| (0..centers.len()).map(move |j| (i, j)))
+            .max_by(|a, b| {
+                let dist_a = dist_sq(&centers[a.0], &centers[a.1]);
+                let dist_b = dist_sq(&centers[b.0], &centers[b.1]);
+                dist_a.partial_cmp(&dist_b).unwrap()
+            })
+            .expect("no shades were found");
+        let start_color = centers[start];
+        let mut ramp: Vec<usize> = (0..centers.len()).collect();
+        ramp.sort_by(|&a, &b| {
+            dist_sq(&centers[a], &start_color)
+                .partial_cmp(&dist_sq(&centers[b], &start_color))
+                .unwrap()
+        });
+
+        let background_idx = (0..centers.len())
+            .max_by_key(|&i| (counts[i], std::cmp::Reverse(i)))
+            .expect("no shades were found");
+        let mut background = ramp.iter().position(|&i| i == background_idx).unwrap();
+
+        // The text is at the end of the ramp farther from the background
+        let last = ramp.len() - 1;
+        let reverse = match (2 * background).cmp(&last) {
+            Ordering::Less => false,
+            Ordering::Greater => true,
+            Ordering::Equal => {
+                let bg_color = &centers[background_idx];
+                dist_sq(&centers[ramp[0]], bg_color) > dist_sq(&centers[ramp[last]], bg_color)
+            }
+        };
+        if reverse {
+            ramp.reverse();
+            background = last - background;
+        }
+
+        // Shades at least as close to the text as to the background are foreground
+        let foreground = (background + 1..=last)
+            .find(|&i| last - i <= i - background)
+            .unwrap_or(last + 1);
+
+        let shades = ramp
+            .iter()
+            .map(|&i| Rgb(centers[i].map(|x| x.round().clamp(0.0, 255.0) as u8)))
+            .collect();
+
+        Palette {
+            shades,
+            background,
+            foreground,
+        }
+    }
+
+    /// Returns the index of the shade closest to the color.
+    pub fn shade_of(&self, color: &Rgb<u8>) -> usize {
+        let shades: Vec<[f32; 3]> = self.shades.iter().map(to_f32).collect();
+        nearest(&shades, &to_f32(color))
+    }
+
+    /// Returns true if the color belongs to the foreground.
+    pub fn is_foreground(&self, color: &Rgb<u8>) -> bool {
+        self.shade_of(color) >= self.foreground
+    }
+
+    /// Maps the image to a binary image.
+    ///
+    /// The foreground has the value of 255, the background the value of 0.
+    pub fn binarize(&self, img: &RgbImage) -> GrayImage {
+        let mut lookup = HashMap::<[u8; 3], u8>::new();
+        let mut binary = GrayImage::new(img.width(), img.height());
+        for (x, y, pixel) in img.enumerate_pixels() {
+            let value = *lookup.entry(pixel.0).or_insert_with(|| {
+                if self.is_foreground(pixel) {
+                    255
+                } else {
+                    0
+                }
+            });
+            binary.put_pixel(x, y, Luma([value]));
+        }
+        binary
+    }
+}
+
+/// Returns the binary image of the Game Boy screen, regardless of its palette.
+///
+/// The background has the value of 0, the text has the value of 255.
+pub fn normalize_palette(img: &DynamicImage) -> GrayImage {
+    let img = img.to_rgb8();
+    let palette = Palette::from_image(&img);
+    palette.binarize(&img)
+}
+
+fn to_f32(color: &Rgb<u8>) -> [f32; 3] {
+    color.0.map(|x| x as f32)
+}
+
+fn dist_sq(a: &[f32; 3], b: &[f32; 3]) -> f32 {
+    (0..3).map(|i| (a[i] - b[i]) * (a[i] - b[i])).sum()
+}
+
+/// Returns the index of the center closest to the color.
+fn nearest(centers: &[[f32; 3]], color: &[f32; 3]) -> usize {
+    let mut best = 0;
+    let mut best_dist = f32::MAX;
+    for (idx, center) in centers.iter().enumerate() {
+        let dist = dist_sq(center, color);
+        if dist < best_dist {
+            best = idx;
+            best_dist = dist;
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::scan_img;
+
+    /// The screenshots that show nothing but the Game Boy screen.
+    const IMAGES: [&[u8]; 5] = [
+        include_bytes!("../data/images/Yellow_summary_1.png"),
+        include_bytes!("../data/images/Yellow_summary_2.png"),
+        include_bytes!("../data/images/Crystal_summary_1.png"),
+        include_bytes!("../data/images/Crystal_summary_2.png"),
+        include_bytes!("../data/images/Crystal_summary_3.png"),
+    ];
+
+    /// Palettes ordered from the lightest to the darkest DMG shade.
+    const PALETTES: [[[u8; 3]; 4]; 5] = [
+        // DMG gray
+        [[255, 255, 255], [170, 170, 170], [85, 85, 85], [0, 0, 0]],
+        // DMG green
+        [[155, 188, 15], [139, 172, 15], [48, 98, 48], [15, 56, 15]],
+        // Inverted gray
+        [[0, 0, 0], [85, 85, 85], [170, 170, 170], [255, 255, 255]],
+        // Text and background with similar luma
+        [[255, 80, 80], [200, 120, 80], [40, 120, 200], [0, 160, 255]],
+        // SGB palette 1-A
+        [[248, 232, 200], [216, 144, 72], [168, 48, 24], [48, 24, 80]],
+    ];
+
+    /// Recolors the image by quantizing its luma to four shades.
+    fn recolor(img: &DynamicImage, palette: &[[u8; 3]; 4]) -> DynamicImage {
+        let luma = img.to_luma8();
+        let mut recolored = RgbImage::new(luma.width(), luma.height());
+        for (x, y, Luma([value])) in luma.enumerate_pixels() {
+            let shade = 3 - (*value as usize * 4 / 256);
+            recolored.put_pixel(x, y, Rgb(palette[shade]));
+        }
+        DynamicImage::ImageRgb8(recolored)
+    }
+
+    #[test]
+    fn normalization_is_palette_independent() {
+        for img in IMAGES {
+            let img = image::load_from_memory(img).unwrap();
+            let expected = normalize_palette(&recolor(&img, &PALETTES[0]));
+
+            for palette in &PALETTES[1..] {
+                let normalized = normalize_palette(&recolor(&img, palette));
+                assert!(normalized == expected, "palette {palette:?} differs");
+            }
+        }
+    }
+
+    #[test]
+    fn scan_recolored_screens() {
+        for img in IMAGES {
+            let img = image::load_from_memory(img).unwrap();
+            let expected = scan_img(img.clone()).expect("could not scan original");
+
+            for palette in &PALETTES {
+                let result = scan_img(recolor(&img, palette));
+                assert_eq!(
+                    result.as_ref(),
+                    Ok(&expected),
+                    "palette {palette:?} differs"
+                );
+            }
+        }
+    }
+}
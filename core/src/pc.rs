@@ -0,0 +1,215 @@
+//! The contents of a box of Bill's PC, put together from several scans.
+//!
+//! The box lists show only a few Pokémon at once, and the scroll position
+//! is not shown. Scrolling keeps most of the entries on the screen, so the
+//! scans are aligned by the entries they have in common. As a box may hold
+//! the same Pokémon several times, a scan matching at more than one position
+//! is rejected instead of guessed.
+
+use crate::gameboy::{BoxEntry, BoxListContent};
+
+/// The Pokémon of a box, as far as they were scanned.
+#[derive(Debug, Default, Clone, PartialEq, serde::Serialize)]
+pub struct BoxInventory {
+    /// The entries from the highest one scanned.
+    entries: Vec<BoxEntry>,
+    /// The index of the first entry shown on the last scan.
+    scroll: usize,
+    /// True once the "CANCEL" at the end of the list was scanned.
+    has_end: bool,
+}
+
+/// Returns true if the entries can be the same Pokémon.
+///
+/// The level and species only tell entries apart if both are known.
+fn is_same_entry(known: &BoxEntry, seen: &BoxEntry) -> bool {
+    let level = known.level.zip(seen.level).is_none_or(|(a, b)| a == b);
+    let species = match (&known.species, &seen.species) {
+        (Some(a), Some(b)) => a == b,
+        _ => true,
+    };
+    known.name == seen.name && level && species
+}
+
+impl BoxInventory {
+    /// Creates an empty inventory.
+    pub fn new() -> BoxInventory {
+        BoxInventory::default()
+    }
+
+    /// Adds the entries of a scan of the box list.
+    ///
+    /// The first scan may be at any scroll position. Every further scan has
+    /// to share an entry with the inventory at exactly one position, the
+    /// entries scrolled above the first scan are put in front.
+    ///
+    /// Returns the scroll position, the index of the first entry shown.
+    pub fn add(&mut self, list: &BoxListContent) -> Result<usize, String> {
+        let offsets = self.alignments(list);
+        let offset = match offsets[..] {
+            [offset] => offset,
+            [] => return Err("the entries do not continue the scanned box list".to_string()),
+            _ => {
+                return Err(format!(
+                    "the entries match the scanned box list at {} positions",
+                    offsets.len()
+                ))
+            }
+        };
+
+        let before = offset.min(0).unsigned_abs();
+        let scroll = offset.max(0) as usize;
+        self.entries
+            .splice(0..0, list.entries[..before].iter().cloned());
+        for (idx, seen) in list.entries.iter().enumerate() {
+            match self.entries.get_mut(scroll + idx) {
+                Some(known) => {
+                    known.level = known.level.or(seen.level);
+                    known.species = known.species.clone().or(seen.species.clone());
+                }
+                None => self.entries.push(seen.clone()),
+            }
+        }
+        self.scroll = scroll;
+        self.has_end |= list.is_end;
+        Ok(scroll)
+    }
+
+    /// Returns the positions of the inventory the list can be shown from.
+    ///
+    /// A negative position puts the first entries of the list above the
+    /// inventory. Any position fits an empty inventory, so it is 0.
+    pub fn alignments(&self, list: &BoxListContent) -> Vec<isize> {
+        if self.entries.is_empty() {
+            return vec![0];
+        }
+        let len = self.entries.len() as isize;
+        let first = 1 - list.entries.len() as isize;
+        (first..len)
+            .filter(|offset| self.fits(*offset, list))
+            .collect()
+    }
+
+    /// Returns true if the list can be shown from the position.
+    fn fits(&self, offset: isize, list: &BoxListContent) -> bool {
+        let len = self.entries.len() as isize;
+        let end = offset + list.entries.len() as isize;
+        let is_length_possible = match (self.has_end, list.is_end) {
+            (true, true) => end == len,
+            (true, false) => end <= len,
+            (false, true) => end >= len,
+            (false, false) => true,
+        };
+        let is_matching = list.entries.iter().enumerate().all(|(idx, seen)| {
+            let pos = offset + idx as isize;
+            !(0..len).contains(&pos) || is_same_entry(&self.entries[pos as usize], seen)
+        });
+        is_length_possible && is_matching
+    }
+
+    /// Returns the entries scanned so far, from the highest one.
+    pub fn entries(&self) -> &[BoxEntry] {
+        &self.entries
+    }
+
+    /// Returns the scroll position of the last scan.
+    pub fn scroll(&self) -> usize {
+        self.scroll
+    }
+
+    /// Returns true if the end of the list was scanned.
+    pub fn has_end(&self) -> bool {
+        self.has_end
+    }
+
+    /// Serializes the inventory into JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("failed to serialize box inventory")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, level: i32) -> BoxEntry {
+        BoxEntry {
+            name: name.to_string(),
+            level: Some(level),
+            species: None,
+        }
+    }
+
+    fn list(entries: &[BoxEntry], is_end: bool) -> BoxListContent {
+        BoxListContent {
+            entries: entries.to_vec(),
+            cursor: 0,
+            is_end,
+        }
+    }
+
+    #[test]
+    fn inventory_from_scrolled_lists() {
+        let [a, b, c, d, e] = [
+            entry("RATTATA", 3),
+            entry("PIDGEY", 5),
+            entry("SPEAROW", 4),
+            entry("ZUBAT", 7),
+            entry("ODDISH", 12),
+        ];
+
+        // Opened in the middle of the list
+        let mut inventory = BoxInventory::new();
+        assert_eq!(inventory.add(&list(&[b.clone(), c.clone()], false)), Ok(0));
+        assert_eq!(inventory.add(&list(&[c.clone(), d.clone()], false)), Ok(1));
+
+        // Scrolled back above the first scan
+        assert_eq!(inventory.add(&list(&[a.clone(), b.clone()], false)), Ok(0));
+        assert_eq!(inventory.add(&list(&[d.clone(), e.clone()], true)), Ok(3));
+        assert!(inventory.has_end());
+        assert_eq!(inventory.entries(), [a, b, c, d, e.clone()]);
+
+        // Nothing follows the end of the list
+        assert!(inventory
+            .add(&list(&[e, entry("ZUBAT", 9)], false))
+            .is_err());
+        assert_eq!(inventory.scroll(), 3);
+
+        let json = BoxInventory::new().to_json();
+        assert_eq!(json, r#"{"entries":[],"scroll":0,"has_end":false}"#);
+    }
+
+    #[test]
+    fn inventory_rejects_ambiguous_lists() {
+        let [a, b] = [entry("RATTATA", 3), entry("PIDGEY", 5)];
+
+        let mut inventory = BoxInventory::new();
+        let top = [a.clone(), b.clone(), a.clone(), b.clone()];
+        assert_eq!(inventory.add(&list(&top, false)), Ok(0));
+
+        // The repeated Pokémon match two rows apart
+        let scrolled = [b.clone(), a.clone(), b.clone(), entry("ODDISH", 12)];
+        assert_eq!(inventory.alignments(&list(&scrolled, false)), [1, 3]);
+        assert!(inventory.add(&list(&scrolled, false)).is_err());
+        assert_eq!(inventory.entries(), top);
+    }
+
+    #[test]
+    fn inventory_fills_in_species() {
+        let mut inventory = BoxInventory::new();
+        let names = ["SPARKY", "BOLT"].map(|name| BoxEntry {
+            name: name.to_string(),
+            level: None,
+            species: None,
+        });
+        inventory.add(&list(&names, true)).unwrap();
+
+        let mut selected = names.clone();
+        selected[1].level = Some(20);
+        selected[1].species = Some("PIKACHU".to_string());
+        inventory.add(&list(&selected, true)).unwrap();
+
+        assert_eq!(inventory.entries()[1], selected[1]);
+        assert_eq!(inventory.entries()[0].species, None);
+    }
+}
@@ -1,3 +1,18 @@
+use crate::stats::GrowthRate;
+
+/// Returns the name of the Pokemon as shown in the game.
+///
+/// The games use upper case names, and the Nidorans are told apart by the
+/// gender symbols.
+pub fn ingame_name(name: &str) -> String {
+    match name {
+        "Nidoran-F" => "NIDORAN♀".to_string(),
+        "Nidoran-M" => "NIDORAN♂".to_string(),
+        "Mr. Mime" => "MR.MIME".to_string(),
+        _ => name.to_uppercase(),
+    }
+}
+
 #[derive(Debug, serde::Deserialize)]
 pub struct RbyPokemon {
     pub ndex: i32,
@@ -13,6 +28,14 @@ pub struct RbyPokemon {
     pub speed: i32,
     #[serde(rename = "spa")]
     pub special: i32,
+    #[serde(skip)]
+    pub growth_rate: GrowthRate,
+}
+
+#[derive(serde::Deserialize)]
+struct RbyGrowthRate {
+    name: String,
+    growth_rate: GrowthRate,
 }
 
 /// The Pokedex with the RBY Pokemon.
@@ -35,6 +58,21 @@ impl RbyPokedex {
             pokedex.push(record);
         }
 
+        // Only the species that do not grow at the medium fast rate are listed
+        const CSV_RATES: &str = include_str!("../data/rby_growth_rates.csv");
+        let mut csv_reader = csv::ReaderBuilder::new()
+            .has_headers(true)
+            .from_reader(CSV_RATES.as_bytes());
+
+        for result in csv_reader.deserialize() {
+            let record: RbyGrowthRate = result.expect("could not deserialize growth rate");
+            let pokemon = pokedex
+                .iter_mut()
+                .find(|p| p.name == record.name)
+                .expect("could not find Pokemon of growth rate");
+            pokemon.growth_rate = record.growth_rate;
+        }
+
         RbyPokedex { pokemon: pokedex }
     }
 
@@ -44,11 +82,21 @@ impl RbyPokedex {
         pokemon
     }
 
+    /// Returns a reference to the Pokemon corresponding to the name shown in the game.
+    pub fn get_ingame(&self, name: &str) -> Option<&RbyPokemon> {
+        self.pokemon.iter().find(|p| ingame_name(&p.name) == name)
+    }
+
     /// Returns a reference to the Pokemon corresponding to the national dex number.
     pub fn get_ndex(&self, ndex: usize) -> Option<&RbyPokemon> {
         let pokemon = self.pokemon.get(ndex - 1); // Pokemon are stored in order
         pokemon
     }
+
+    /// Returns the names of the Pokemon as shown in the game.
+    pub fn ingame_names(&self) -> Vec<String> {
+        self.pokemon.iter().map(|p| ingame_name(&p.name)).collect()
+    }
 }
 
 #[derive(Debug, serde::Deserialize)]
@@ -68,6 +116,41 @@ pub struct GscPokemon {
     pub special_defense: i32,
     #[serde(rename = "spe")]
     pub speed: i32,
+    /// The share of females, None if genderless.
+    #[serde(skip)]
+    pub female_ratio: Option<f32>,
+}
+
+/// The gender of a Pokemon, as shown by its symbol.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Gender {
+    Male,
+    Female,
+}
+
+impl Gender {
+    /// Returns the gender of the symbol, "♂" or "♀".
+    pub fn from_symbol(symbol: &str) -> Option<Gender> {
+        match symbol {
+            "♂" => Some(Gender::Male),
+            "♀" => Some(Gender::Female),
+            _ => None,
+        }
+    }
+
+    /// Returns the symbol of the gender.
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Gender::Male => "♂",
+            Gender::Female => "♀",
+        }
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct GenderRatio {
+    name: String,
+    female_ratio: Option<f32>,
 }
 
 /// The Pokedex with the GSC Pokemon.
@@ -86,10 +169,26 @@ impl GscPokedex {
             .from_reader(CSV_DATA.as_bytes());
 
         for result in csv_reader.deserialize() {
-            let record: GscPokemon = result.expect("could not deserialize Pokemon");
+            let mut record: GscPokemon = result.expect("could not deserialize Pokemon");
+            record.female_ratio = Some(0.5);
             pokedex.push(record);
         }
 
+        // Only the species that are not half female are listed
+        const CSV_RATIOS: &str = include_str!("../data/gsc_gender_ratios.csv");
+        let mut csv_reader = csv::ReaderBuilder::new()
+            .has_headers(true)
+            .from_reader(CSV_RATIOS.as_bytes());
+
+        for result in csv_reader.deserialize() {
+            let record: GenderRatio = result.expect("could not deserialize gender ratio");
+            let pokemon = pokedex
+                .iter_mut()
+                .find(|p| p.name == record.name)
+                .expect("could not find Pokemon of gender ratio");
+            pokemon.female_ratio = record.female_ratio;
+        }
+
         GscPokedex { pokemon: pokedex }
     }
 
@@ -99,9 +198,19 @@ impl GscPokedex {
         pokemon
     }
 
+    /// Returns a reference to the Pokemon corresponding to the name shown in the game.
+    pub fn get_ingame(&self, name: &str) -> Option<&GscPokemon> {
+        self.pokemon.iter().find(|p| ingame_name(&p.name) == name)
+    }
+
     /// Returns a reference to the Pokemon corresponding to the national dex number.
     pub fn get_ndex(&self, ndex: usize) -> Option<&GscPokemon> {
         let pokemon = self.pokemon.get(ndex - 1); // Pokemon are stored in order
         pokemon
     }
+
+    /// Returns the names of the Pokemon as shown in the game.
+    pub fn ingame_names(&self) -> Vec<String> {
+        self.pokemon.iter().map(|p| ingame_name(&p.name)).collect()
+    }
 }
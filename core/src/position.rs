@@ -6,7 +6,7 @@ use std::convert::TryFrom;
 ///
 /// The elements on the border are part of the thing. Therefore a single pixel
 /// would contain the pixel coordinates and the height and width of 1.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct Position {
     pub x: u32,
     pub y: u32,
@@ -14,6 +14,17 @@ pub struct Position {
     pub height: u32,
 }
 
+impl Position {
+    /// Returns the coordinates just past the bottom right corner.
+    ///
+    /// Returns `None` if they do not fit into `u32`.
+    pub fn end(&self) -> Option<(u32, u32)> {
+        let x = self.x.checked_add(self.width)?;
+        let y = self.y.checked_add(self.height)?;
+        Some((x, y))
+    }
+}
+
 impl TryFrom<&Contour<i32>> for Position {
     type Error = &'static str;
 
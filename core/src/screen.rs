@@ -0,0 +1,435 @@
+//! Classification of the game screens.
+//!
+//! Every screen shows some static text, like the "TYPE1/" label of the RBY
+//! summary. Comparing the decoded tile map against these texts tells which
+//! screen is shown, without a dedicated check for each of them.
+//!
+//! A few screens show the same static text in RBY and GSC, like the FIGHT
+//! menu. These are told apart by the font, as the "/" of RBY is drawn a
+//! pixel lower than that of GSC.
+
+use crate::tilemap::{TileMap, TILE_COLUMNS, TILE_ROWS};
+
+/// The minimal score of a recognized screen.
+pub const MIN_SCORE: f32 = 0.8;
+
+/// The screens known by the classifier.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ScreenKind {
+    RbySummary1,
+    RbySummary2,
+    GscSummary1,
+    GscSummary2,
+    GscSummary3,
+    RbyLevelUp,
+    GscLevelUp,
+    RbyPokedexEntry,
+    GscPokedexEntry,
+    RbyTrainerCard,
+    GscTrainerCard,
+    GscBadgePage,
+    RbyBattle,
+    GscBattle,
+    RbyFightMenu,
+    GscFightMenu,
+    RbyParty,
+    GscParty,
+}
+
+impl ScreenKind {
+    /// Returns true if the screen is of RBY.
+    pub fn is_rby(&self) -> bool {
+        matches!(
+            self,
+            ScreenKind::RbySummary1
+                | ScreenKind::RbySummary2
+                | ScreenKind::RbyLevelUp
+                | ScreenKind::RbyPokedexEntry
+                | ScreenKind::RbyTrainerCard
+                | ScreenKind::RbyBattle
+                | ScreenKind::RbyFightMenu
+                | ScreenKind::RbyParty
+        )
+    }
+}
+
+/// A static text of a screen at the given tile coordinates.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Signature {
+    pub col: u32,
+    pub row: u32,
+    pub text: &'static str,
+    /// True if the text must not be shown, to tell the screen apart from
+    /// another one showing the same texts and more.
+    pub absent: bool,
+}
+
+impl Signature {
+    pub fn new(col: u32, row: u32, text: &'static str) -> Signature {
+        Signature {
+            col,
+            row,
+            text,
+            absent: false,
+        }
+    }
+
+    /// Creates the signature of a text that must not be shown.
+    pub fn absent(col: u32, row: u32, text: &'static str) -> Signature {
+        Signature {
+            absent: true,
+            ..Signature::new(col, row, text)
+        }
+    }
+
+    /// Returns the number of characters found at their place on the tile
+    /// map, or missing from it if the text must be absent.
+    fn matches(&self, tilemap: &TileMap) -> usize {
+        let found = self
+            .text
+            .chars()
+            .zip(self.col..)
+            .filter(|(expected, col)| {
+                let Some(tile) = tilemap.get(*col, self.row) else {
+                    return false;
+                };
+                tile.char.as_deref() == Some(expected.encode_utf8(&mut [0; 4]))
+            })
+            .count();
+        match self.absent {
+            true => self.text.chars().count() - found,
+            false => found,
+        }
+    }
+}
+
+/// The result of the classification.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Classification {
+    pub kind: ScreenKind,
+    /// The ratio of the signature characters found on the screen.
+    pub score: f32,
+}
+
+/// Recognizes screens by their static texts.
+pub struct ScreenClassifier {
+    screens: Vec<(ScreenKind, Vec<Signature>)>,
+}
+
+impl ScreenClassifier {
+    /// Creates a classifier that knows the summary screens, the level-up
+    /// stats boxes, the Pokédex entries, the trainer cards with the badge
+    /// pages of GSC, the battle menu, the FIGHT menu and the party menu of
+    /// RBY and GSC.
+    pub fn new() -> ScreenClassifier {
+        let mut classifier = ScreenClassifier {
+            screens: Vec::new(),
+        };
+
+        classifier.add(
+            ScreenKind::RbySummary1,
+            vec![
+                Signature::new(9, 6, "STATUS/"),
+                Signature::new(1, 9, "ATTACK"),
+                Signature::new(1, 11, "DEFENSE"),
+                Signature::new(1, 13, "SPEED"),
+                Signature::new(1, 15, "SPECIAL"),
+                Signature::new(10, 9, "TYPE1/"),
+                Signature::new(10, 15, "OT/"),
+            ],
+        );
+        classifier.add(
+            ScreenKind::RbySummary2,
+            vec![
+                Signature::new(9, 3, "EXP POINTS"),
+                Signature::new(9, 5, "LEVEL UP"),
+                Signature::new(16, 10, "/"), // PP of the first move
+            ],
+        );
+        classifier.add(
+            ScreenKind::GscSummary1,
+            vec![
+                Signature::new(10, 9, "EXP POINTS"),
+                Signature::new(10, 12, "LEVEL UP"),
+                Signature::new(0, 12, "STATUS/"),
+                Signature::new(0, 14, "TYPE/"),
+                Signature::new(4, 10, "/"), // HP divider
+            ],
+        );
+        classifier.add(
+            ScreenKind::GscSummary2,
+            vec![
+                Signature::new(0, 8, "ITEM"),
+                Signature::new(0, 10, "MOVE"),
+                Signature::new(17, 11, "/"), // PP of the first move
+            ],
+        );
+        classifier.add(
+            ScreenKind::GscSummary3,
+            vec![
+                Signature::new(0, 12, "OT/"),
+                Signature::new(11, 8, "ATTACK"),
+                Signature::new(11, 10, "DEFENSE"),
+                Signature::new(11, 16, "SPEED"),
+            ],
+        );
+        classifier.add(
+            ScreenKind::RbyLevelUp,
+            vec![
+                Signature::new(11, 3, "ATTACK"),
+                Signature::new(11, 5, "DEFENSE"),
+                Signature::new(11, 7, "SPEED"),
+                Signature::new(11, 9, "SPECIAL"),
+            ],
+        );
+        classifier.add(
+            ScreenKind::GscLevelUp,
+            vec![
+                Signature::new(11, 1, "ATTACK"),
+                Signature::new(11, 3, "DEFENSE"),
+                Signature::new(11, 5, "SPCL.ATK"),
+                Signature::new(11, 7, "SPCL.DEF"),
+                Signature::new(11, 9, "SPEED"),
+            ],
+        );
+        classifier.add(
+            ScreenKind::RbyPokedexEntry,
+            vec![
+                Signature::new(9, 6, "HT"),
+                Signature::new(9, 8, "WT"),
+                Signature::new(17, 8, "lb"),
+            ],
+        );
+        classifier.add(
+            ScreenKind::GscPokedexEntry,
+            vec![
+                Signature::new(9, 7, "HT"),
+                Signature::new(9, 9, "WT"),
+                Signature::new(17, 9, "lb"),
+            ],
+        );
+        classifier.add(
+            ScreenKind::RbyTrainerCard,
+            vec![
+                Signature::new(2, 2, "NAME/"),
+                Signature::new(2, 4, "MONEY/"),
+                Signature::new(2, 6, "TIME/"),
+            ],
+        );
+        classifier.add(
+            ScreenKind::GscTrainerCard,
+            vec![
+                Signature::new(2, 2, "NAME/"),
+                Signature::new(2, 6, "MONEY"),
+                Signature::new(2, 12, "PLAY TIME"),
+            ],
+        );
+        // The badge pages keep only the name of the first page, the other
+        // texts of the trainer cards must be missing
+        classifier.add(
+            ScreenKind::GscBadgePage,
+            vec![
+                Signature::new(2, 2, "NAME/"),
+                Signature::absent(2, 4, "MONEY"),
+                Signature::absent(2, 6, "MONEY"),
+                Signature::absent(2, 12, "PLAY TIME"),
+            ],
+        );
+        // The battle screen is recognized by the menu in its text box
+        classifier.add(
+            ScreenKind::RbyBattle,
+            vec![
+                Signature::new(10, 14, "FIGHT"),
+                Signature::new(10, 16, "ITEM"),
+                Signature::new(16, 16, "RUN"),
+            ],
+        );
+        classifier.add(
+            ScreenKind::GscBattle,
+            vec![
+                Signature::new(10, 14, "FIGHT"),
+                Signature::new(10, 16, "PACK"),
+                Signature::new(16, 16, "RUN"),
+            ],
+        );
+        let fight_menu = vec![
+            Signature::new(1, 9, "TYPE/"),
+            Signature::new(7, 11, "/"), // PP divider
+        ];
+        classifier.add(ScreenKind::RbyFightMenu, fight_menu.clone());
+        classifier.add(ScreenKind::GscFightMenu, fight_menu);
+        classifier.add(
+            ScreenKind::RbyParty,
+            vec![
+                Signature::new(1, 14, "Choose a POKéMON."),
+                Signature::new(16, 1, "/"), // HP divider of the first Pokémon
+            ],
+        );
+        classifier.add(
+            ScreenKind::GscParty,
+            vec![
+                Signature::new(1, 16, "Choose a POKéMON."),
+                Signature::new(16, 1, "/"), // HP divider of the first Pokémon
+            ],
+        );
+
+        classifier
+    }
+
+    /// Adds a screen with its signatures to the classifier.
+    pub fn add(&mut self, kind: ScreenKind, signatures: Vec<Signature>) {
+        self.screens.push((kind, signatures));
+    }
+
+    /// Returns the ratio of the signature characters of the screen found on the tile map.
+    pub fn score(&self, tilemap: &TileMap, kind: ScreenKind) -> f32 {
+        let Some((_, signatures)) = self.screens.iter().find(|(k, _)| *k == kind) else {
+            return 0.0;
+        };
+
+        let total: usize = signatures.iter().map(|s| s.text.chars().count()).sum();
+        if total == 0 {
+            return 0.0;
+        }
+        let found: usize = signatures.iter().map(|s| s.matches(tilemap)).sum();
+        found as f32 / total as f32
+    }
+
+    /// Returns the best matching screen, if its score reaches [`MIN_SCORE`].
+    ///
+    /// Of the screens with the same score, the one of the game with the font
+    /// of the tile map is taken, see [`has_rby_font`].
+    pub fn classify(&self, tilemap: &TileMap) -> Option<Classification> {
+        let is_rby = has_rby_font(tilemap);
+        let mut best: Option<Classification> = None;
+        for (kind, _) in &self.screens {
+            let score = self.score(tilemap, *kind);
+            let is_better = match best {
+                Some(best) if score == best.score => {
+                    best.kind.is_rby() != is_rby && kind.is_rby() == is_rby
+                }
+                Some(best) => score > best.score,
+                None => true,
+            };
+            if is_better {
+                best = Some(Classification { kind: *kind, score });
+            }
+        }
+        best.filter(|b| b.score >= MIN_SCORE)
+    }
+}
+
+impl Default for ScreenClassifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns true if the tile map shows the "/" of RBY.
+///
+/// The "/" of RBY leaves the first pixel row of its tile blank and reaches
+/// the last one, while that of GSC starts at the first row.
+pub fn has_rby_font(tilemap: &TileMap) -> bool {
+    let first_row: u64 = 0x0000_0000_0000_00FF;
+    (0..TILE_ROWS)
+        .flat_map(|row| (0..TILE_COLUMNS).map(move |col| (col, row)))
+        .filter_map(|(col, row)| tilemap.get(col, row))
+        .any(|tile| tile.char.as_deref() == Some("/") && tile.hash & first_row == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ocr::CharTable;
+    use crate::palette::normalize_palette;
+
+    fn read_tilemap(bytes: &[u8], chars: &CharTable) -> TileMap {
+        let img = image::load_from_memory(bytes).unwrap();
+        let img = normalize_palette(&img);
+        TileMap::read(&img, chars).unwrap()
+    }
+
+    #[test]
+    fn classify_screens() {
+        let chars = CharTable::new();
+        let classifier = ScreenClassifier::new();
+
+        let cases: [(&[u8], Option<ScreenKind>); 6] = [
+            (
+                include_bytes!("../data/images/Yellow_summary_1.png"),
+                Some(ScreenKind::RbySummary1),
+            ),
+            (
+                include_bytes!("../data/images/Yellow_summary_2.png"),
+                Some(ScreenKind::RbySummary2),
+            ),
+            (
+                include_bytes!("../data/images/Crystal_summary_1.png"),
+                Some(ScreenKind::GscSummary1),
+            ),
+            (
+                include_bytes!("../data/images/Crystal_summary_2.png"),
+                Some(ScreenKind::GscSummary2),
+            ),
+            (
+                include_bytes!("../data/images/Crystal_summary_3.png"),
+                Some(ScreenKind::GscSummary3),
+            ),
+            (
+                include_bytes!("../data/images/Yellow_nicknaming_upper.png"),
+                None,
+            ),
+        ];
+
+        for (bytes, expected) in cases {
+            let tilemap = read_tilemap(bytes, &chars);
+            let result = classifier.classify(&tilemap);
+            assert_eq!(result.map(|c| c.kind), expected);
+            if let Some(result) = result {
+                assert_eq!(result.score, 1.0);
+            }
+        }
+    }
+
+    #[test]
+    fn tell_fonts_apart() {
+        let chars = CharTable::new();
+        let cases: [(&[u8], bool); 6] = [
+            (include_bytes!("../data/images/Yellow_summary_1.png"), true),
+            (include_bytes!("../data/images/Yellow_summary_2.png"), true),
+            (
+                include_bytes!("../data/images/Yellow_nicknaming_upper.png"),
+                true,
+            ),
+            (
+                include_bytes!("../data/images/Crystal_summary_1.png"),
+                false,
+            ),
+            (
+                include_bytes!("../data/images/Crystal_summary_3.png"),
+                false,
+            ),
+            (
+                include_bytes!("../data/images/Crystal_nicknaming_upper.png"),
+                false,
+            ),
+        ];
+
+        for (bytes, is_rby) in cases {
+            let tilemap = read_tilemap(bytes, &chars);
+            assert_eq!(has_rby_font(&tilemap), is_rby);
+        }
+    }
+
+    #[test]
+    fn other_screen_scores_low() {
+        let chars = CharTable::new();
+        let classifier = ScreenClassifier::new();
+
+        let tilemap = read_tilemap(
+            include_bytes!("../data/images/Yellow_summary_1.png"),
+            &chars,
+        );
+        assert!(classifier.score(&tilemap, ScreenKind::RbySummary2) < MIN_SCORE);
+    }
+}
@@ -0,0 +1,161 @@
+//! The knowledge gathered over the scans of a play session.
+//!
+//! A screen shows only a part of the player's data. The summary shows the
+//! species of a nicknamed Pokémon, while the level-up stats show only its
+//! nickname. The session remembers what the earlier screens have shown, so
+//! that the later ones can be understood.
+
+use crate::gameboy::{GscCardPage, TrainerCardContent};
+use crate::pc::BoxInventory;
+use std::collections::HashMap;
+
+/// The player's data seen on the earlier screens.
+pub struct ScanSession {
+    /// The species of the player's Pokémon by their in-game name, e.g.
+    /// "SPARKY" to "Pikachu".
+    species: HashMap<String, String>,
+    /// The species of the party members, as shown on the party menu
+    /// scanned last.
+    party: Vec<String>,
+    /// The box of Bill's PC scanned last.
+    box_inventory: BoxInventory,
+    /// The trainer card scanned last.
+    trainer: Option<TrainerCardContent>,
+    /// The badges obtained, as shown on the trainer card or given by the
+    /// player.
+    badges: Vec<&'static str>,
+    /// The page of the GSC trainer card scanned last, with the pixels of its
+    /// faces and badges.
+    gsc_card_page: Option<(GscCardPage, Vec<u8>)>,
+}
+
+impl ScanSession {
+    /// Creates a session that has seen no screens yet.
+    pub fn new() -> ScanSession {
+        ScanSession {
+            species: HashMap::new(),
+            party: Vec::new(),
+            box_inventory: BoxInventory::new(),
+            trainer: None,
+            badges: Vec::new(),
+            gsc_card_page: None,
+        }
+    }
+
+    /// Records the species of a Pokémon, as shown on its summary.
+    pub fn record_species(&mut self, name: &str, species: &str) {
+        self.species.insert(name.to_string(), species.to_string());
+    }
+
+    /// Returns the species of a Pokémon by its in-game name, if known.
+    pub fn species(&self, name: &str) -> Option<&str> {
+        self.species.get(name).map(|species| species.as_str())
+    }
+
+    /// Records the species of the party members, as shown on the party menu.
+    ///
+    /// The members whose species is unknown are left out.
+    pub fn record_party(&mut self, species: Vec<String>) {
+        self.party = species;
+    }
+
+    /// Returns the species of the party members, empty until the party menu
+    /// was scanned.
+    pub fn party(&self) -> &[String] {
+        &self.party
+    }
+
+    /// Returns the box of Bill's PC scanned last.
+    pub fn box_inventory(&self) -> &BoxInventory {
+        &self.box_inventory
+    }
+
+    /// Returns the box of Bill's PC scanned last, to add the scans to.
+    pub fn box_inventory_mut(&mut self) -> &mut BoxInventory {
+        &mut self.box_inventory
+    }
+
+    /// Records the player, as shown on the trainer card.
+    pub fn record_trainer(&mut self, trainer: TrainerCardContent) {
+        self.trainer = Some(trainer);
+    }
+
+    /// Returns the player, if the trainer card was scanned.
+    pub fn trainer(&self) -> Option<&TrainerCardContent> {
+        self.trainer.as_ref()
+    }
+
+    /// Records the badges obtained of a set, replacing those recorded of it.
+    ///
+    /// The sets are the badges of RBY and the Johto and Kanto badges of GSC.
+    pub fn record_badges(&mut self, set: &[&'static str], obtained: &[&'static str]) {
+        self.badges.retain(|badge| !set.contains(badge));
+        self.badges.extend_from_slice(obtained);
+    }
+
+    /// Returns the badges obtained.
+    pub fn badges(&self) -> &[&'static str] {
+        &self.badges
+    }
+
+    /// Records that the first page of the GSC trainer card was scanned.
+    pub fn record_gsc_first_page(&mut self) {
+        self.gsc_card_page = Some((GscCardPage::First, Vec::new()));
+    }
+
+    /// Returns the page of the GSC trainer card that shows the faces and
+    /// badges, and records it.
+    ///
+    /// The Johto and Kanto pages are told apart by their order: the Johto
+    /// page follows the first page and the Kanto page follows the Johto page.
+    /// A page showing the same pixels as the page scanned last is that page.
+    pub fn turn_gsc_card_page(&mut self, leaders: Vec<u8>) -> Result<GscCardPage, String> {
+        let page = match &self.gsc_card_page {
+            Some((page, last)) if *last == leaders => *page,
+            Some((GscCardPage::First, _)) => GscCardPage::Johto,
+            Some((GscCardPage::Johto, _)) => GscCardPage::Kanto,
+            _ => {
+                return Err(
+                    "could not tell the badge page, scan the first page of the trainer card and turn the pages"
+                        .to_string(),
+                )
+            }
+        };
+        self.gsc_card_page = Some((page, leaders));
+        Ok(page)
+    }
+}
+
+impl Default for ScanSession {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gsc_card_pages_in_order() {
+        let mut session = ScanSession::new();
+        assert!(session.turn_gsc_card_page(vec![1]).is_err());
+
+        session.record_gsc_first_page();
+        assert_eq!(session.turn_gsc_card_page(vec![1]), Ok(GscCardPage::Johto));
+        assert_eq!(session.turn_gsc_card_page(vec![1]), Ok(GscCardPage::Johto));
+        assert_eq!(session.turn_gsc_card_page(vec![2]), Ok(GscCardPage::Kanto));
+        assert_eq!(session.turn_gsc_card_page(vec![2]), Ok(GscCardPage::Kanto));
+        // Back on the first page is not seen, the order is lost
+        assert!(session.turn_gsc_card_page(vec![1]).is_err());
+    }
+
+    #[test]
+    fn badges_by_set() {
+        let mut session = ScanSession::new();
+        session.record_badges(&["Zephyr", "Hive"], &["Zephyr"]);
+        session.record_badges(&["Boulder", "Cascade"], &["Boulder", "Cascade"]);
+        session.record_badges(&["Zephyr", "Hive"], &["Zephyr", "Hive"]);
+        assert_eq!(session.badges(), ["Boulder", "Cascade", "Zephyr", "Hive"]);
+    }
+}
@@ -3,6 +3,7 @@
 //! Sources:
 //! https://www.smogon.com/ingame/guides/rby_gsc_stats.
 
+use crate::pokemon::Gender;
 use std::ops::Deref;
 
 /// The extent to which a stat varies with respect to DV values.
@@ -19,8 +20,8 @@ impl StatVariation {
     pub fn init(level: &i32, base: &i32, exp: &i32, is_hp: &bool) -> StatVariation {
         let offset = if *is_hp { level + 10 } else { 5 };
 
-        let effort_gain = ((exp - 1) as f32).sqrt() + 1.0 / 4.0;
-        let effort_gain = effort_gain as i32;
+        let effort_gain = ((exp - 1).max(0) as f32).sqrt() as i32 + 1;
+        let effort_gain = effort_gain.min(255) / 4;
 
         let variation = std::array::from_fn(|i| {
             let dv = i as i32;
@@ -31,6 +32,14 @@ impl StatVariation {
 
         StatVariation { values: variation }
     }
+
+    /// Returns the lowest and highest value of the stat.
+    pub fn range(&self) -> StatRange {
+        StatRange {
+            min: self.values[0],
+            max: self.values[15],
+        }
+    }
 }
 
 impl Deref for StatVariation {
@@ -41,6 +50,135 @@ impl Deref for StatVariation {
     }
 }
 
+/// The highest stat experience of a stat.
+pub const MAX_STAT_EXP: i32 = 65535;
+
+/// The range of possible values of a stat, with both ends being inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatRange {
+    pub min: i32,
+    pub max: i32,
+}
+
+/// The number of pixels of a full HP bar.
+pub const HP_BAR_PIXELS: i32 = 48;
+
+/// Returns the number of pixels of the HP bar filled by the HP.
+///
+/// As the games divide 8-bit numbers, a max HP above 255 is divided by 4
+/// first. A Pokémon with HP left always fills at least a pixel.
+pub fn hp_bar_pixels(hp: i32, max_hp: i32) -> i32 {
+    if hp == 0 {
+        return 0;
+    }
+    let pixels = if max_hp > 255 {
+        hp * HP_BAR_PIXELS / 4 / (max_hp / 4)
+    } else {
+        hp * HP_BAR_PIXELS / max_hp
+    };
+    pixels.max(1)
+}
+
+/// Returns the range of HP filling the pixels of the HP bar.
+///
+/// Every possible max HP is tried, so the range widens with the variation
+/// of the max HP.
+pub fn hp_from_bar(pixels: i32, max_hp: &StatVariation) -> Result<StatRange, String> {
+    let hps: Vec<i32> = max_hp
+        .iter()
+        .flat_map(|max_hp| (0..=*max_hp).filter(|hp| hp_bar_pixels(*hp, *max_hp) == pixels))
+        .collect();
+
+    match (hps.iter().min(), hps.iter().max()) {
+        (Some(min), Some(max)) => Ok(StatRange {
+            min: *min,
+            max: *max,
+        }),
+        _ => Err(format!(
+            "HP bar of {pixels} pixels not possible with max HP '{:?}'",
+            max_hp.values
+        )),
+    }
+}
+
+/// The Attack, Defense, Speed and Special DV of the Pokémon of trainers.
+///
+/// All trainers of RBY use them. In GSC, the DVs depend on the trainer class.
+pub const TRAINER_DVS: [i32; 4] = [9, 8, 8, 8];
+
+/// Returns the HP DV, made of the lowest bits of the other DVs.
+pub fn hp_dv(attack: i32, defense: i32, speed: i32, special: i32) -> i32 {
+    (attack & 1) << 3 | (defense & 1) << 2 | (speed & 1) << 1 | special & 1
+}
+
+/// The badges of RBY boosting a stat, with the stat.
+///
+/// In battle, the stats of the player's Pokémon are boosted by the badges.
+pub const RBY_BADGE_BOOSTS: [(&str, &str); 4] = [
+    ("Boulder", "Attack"),
+    ("Thunder", "Defense"),
+    ("Soul", "Speed"),
+    ("Volcano", "Special"),
+];
+
+/// Returns the stat boosted by a badge, by an eighth and up to 999.
+pub fn badge_boost(stat: i32) -> i32 {
+    (stat + stat / 8).min(999)
+}
+
+/// The highest level of a Pokémon.
+pub const MAX_LEVEL: i32 = 100;
+
+/// The rate at which a species gains levels.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, serde::Deserialize)]
+pub enum GrowthRate {
+    Fast,
+    #[default]
+    #[serde(rename = "Medium Fast")]
+    MediumFast,
+    #[serde(rename = "Medium Slow")]
+    MediumSlow,
+    Slow,
+}
+
+impl GrowthRate {
+    /// Returns the total EXP points needed to reach the level.
+    pub fn exp(&self, level: i32) -> i32 {
+        let cube = level * level * level;
+        match self {
+            GrowthRate::Fast => cube * 4 / 5,
+            GrowthRate::MediumFast => cube,
+            GrowthRate::MediumSlow => cube * 6 / 5 - 15 * level * level + 100 * level - 140,
+            GrowthRate::Slow => cube * 5 / 4,
+        }
+    }
+
+    /// Returns an error if the EXP points do not belong to the level.
+    ///
+    /// The EXP points needed for the next level are the EXP points of the
+    /// Pokémon plus those left to level up.
+    pub fn verify_exp(&self, level: i32, exp: i32, exp_to_next: i32) -> Result<(), String> {
+        let next_exp = self.exp(level + 1);
+        let is_below_level = level > 1 && exp < self.exp(level);
+        let is_above_level = level < MAX_LEVEL && exp >= next_exp;
+        if is_below_level || is_above_level {
+            return Err(format!("{exp} EXP points do not match level {level}"));
+        }
+        if level < MAX_LEVEL && exp + exp_to_next != next_exp {
+            return Err(format!(
+                "{exp_to_next} EXP points to level up do not match level {level}"
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// The Attack DVs of shiny Pokémon in GSC.
+pub const SHINY_ATTACK_DVS: [i32; 8] = [2, 3, 6, 7, 10, 11, 14, 15];
+
+/// The Defense, Speed and Special DV of shiny Pokémon in GSC.
+pub const SHINY_DV: i32 = 10;
+
 /// The range of possible DV values for a stat, with both ends being inclusive.
 pub struct DvRange {
     pub min: i32,
@@ -64,4 +202,178 @@ impl DvRange {
             )),
         }
     }
+
+    /// Inits the DvRange from a stat value of unknown stat experience.
+    ///
+    /// A DV is possible if the stat lies between its values with the least
+    /// and the most stat experience.
+    pub fn init_within(
+        current_stat: &i32,
+        lowest: &StatVariation,
+        highest: &StatVariation,
+    ) -> Result<DvRange, String> {
+        let is_possible = |dv: usize| lowest[dv] <= *current_stat && *current_stat <= highest[dv];
+        let first = (0..16).position(is_possible);
+        let last = (0..16).rposition(is_possible);
+
+        match (first, last) {
+            (Some(a), Some(b)) => Ok(DvRange {
+                min: a as i32,
+                max: b as i32,
+            }),
+            _ => Err(format!(
+                "stat value '{}' not between stat variations '{:?}' and '{:?}'",
+                current_stat, lowest.values, highest.values
+            )),
+        }
+    }
+
+    /// Returns true if the DV value is within the range.
+    pub fn contains(&self, dv: i32) -> bool {
+        self.min <= dv && dv <= self.max
+    }
+}
+
+/// Returns the gender of a GSC Pokémon from its Attack DV.
+///
+/// The Pokémon is female if its Attack DV is among the lowest ones, the
+/// share of which is the share of females of the species. Returns None for
+/// genderless species.
+pub fn gsc_gender(attack_dv: i32, female_ratio: Option<f32>) -> Option<Gender> {
+    let female_ratio = female_ratio?;
+    if (attack_dv as f32) < female_ratio * 16.0 {
+        Some(Gender::Female)
+    } else {
+        Some(Gender::Male)
+    }
+}
+
+/// Returns an error if no Attack DV within the range gives the gender.
+pub fn verify_gsc_gender(
+    gender: Option<Gender>,
+    attack: &DvRange,
+    female_ratio: Option<f32>,
+) -> Result<(), String> {
+    if (attack.min..=attack.max).any(|dv| gsc_gender(dv, female_ratio) == gender) {
+        return Ok(());
+    }
+    let gender = gender.map_or("none", |x| x.symbol());
+    Err(format!(
+        "gender {gender} does not match Attack DV range {}-{}",
+        attack.min, attack.max
+    ))
+}
+
+/// Returns an error if the DV ranges contradict the shiny star.
+///
+/// A shiny Pokémon has a Defense, Speed and Special DV of 10 and one of
+/// [`SHINY_ATTACK_DVS`]. The Special DV is shared by both special stats.
+pub fn verify_gsc_shiny(
+    shiny: bool,
+    attack: &DvRange,
+    defense: &DvRange,
+    speed: &DvRange,
+    special: &[&DvRange; 2],
+) -> Result<(), String> {
+    let others = [defense, speed, special[0], special[1]];
+    let can_be_shiny = SHINY_ATTACK_DVS.iter().any(|dv| attack.contains(*dv))
+        && others.iter().all(|range| range.contains(SHINY_DV));
+    let must_be_shiny = SHINY_ATTACK_DVS.contains(&attack.min)
+        && attack.min == attack.max
+        && others
+            .iter()
+            .all(|range| range.min == SHINY_DV && range.max == SHINY_DV);
+
+    match (shiny, can_be_shiny, must_be_shiny) {
+        (true, false, _) => Err("shiny star does not match the DV ranges".to_string()),
+        (false, _, true) => Err("missing shiny star, though the DVs are shiny".to_string()),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exp_of_growth_rates() {
+        assert_eq!(GrowthRate::MediumFast.exp(5), 125);
+        assert_eq!(GrowthRate::MediumSlow.exp(6), 179);
+        assert_eq!(GrowthRate::Fast.exp(100), 800_000);
+        assert_eq!(GrowthRate::Slow.exp(100), 1_250_000);
+
+        assert!(GrowthRate::MediumSlow.verify_exp(5, 135, 44).is_ok());
+        assert!(GrowthRate::MediumSlow.verify_exp(5, 135, 45).is_err());
+        assert!(GrowthRate::MediumFast.verify_exp(5, 216, 0).is_err());
+        assert!(GrowthRate::MediumFast.verify_exp(100, 1_000_000, 0).is_ok());
+    }
+
+    #[test]
+    fn hp_from_bar_pixels() {
+        // Level 5 Pidgey, with a base HP of 40
+        let max_hp = StatVariation::init(&5, &40, &0, &true);
+        assert_eq!(max_hp.range(), StatRange { min: 19, max: 20 });
+
+        assert_eq!(hp_from_bar(48, &max_hp), Ok(StatRange { min: 19, max: 20 }));
+        assert_eq!(hp_from_bar(24, &max_hp), Ok(StatRange { min: 10, max: 10 }));
+        assert_eq!(hp_from_bar(2, &max_hp), Ok(StatRange { min: 1, max: 1 }));
+        assert_eq!(hp_from_bar(0, &max_hp), Ok(StatRange { min: 0, max: 0 }));
+        assert!(hp_from_bar(49, &max_hp).is_err());
+
+        assert_eq!(hp_bar_pixels(300, 600), 24);
+
+        let [attack, defense, speed, special] = TRAINER_DVS;
+        assert_eq!(hp_dv(attack, defense, speed, special), 8);
+        assert_eq!(hp_bar_pixels(1, 600), 1);
+    }
+
+    #[test]
+    fn dv_range_of_unknown_stat_exp() {
+        // Level 12 Pikachu, with a base Attack of 55
+        let lowest = StatVariation::init(&12, &55, &0, &false);
+        let highest = StatVariation::init(&12, &55, &MAX_STAT_EXP, &false);
+        assert_eq!(lowest.range(), StatRange { min: 18, max: 21 });
+        assert_eq!(highest.range(), StatRange { min: 25, max: 29 });
+
+        let range = DvRange::init(&20, &lowest).unwrap();
+        assert_eq!((range.min, range.max), (8, 11));
+        let range = DvRange::init_within(&20, &lowest, &highest).unwrap();
+        assert_eq!((range.min, range.max), (0, 11));
+        let range = DvRange::init_within(&28, &lowest, &highest).unwrap();
+        assert_eq!((range.min, range.max), (10, 15));
+        assert!(DvRange::init_within(&30, &lowest, &highest).is_err());
+    }
+
+    #[test]
+    fn badge_boosts() {
+        assert_eq!(badge_boost(100), 112);
+        assert_eq!(badge_boost(7), 7);
+        assert_eq!(badge_boost(950), 999);
+    }
+
+    #[test]
+    fn gender_from_attack_dv() {
+        assert_eq!(gsc_gender(1, Some(0.125)), Some(Gender::Female));
+        assert_eq!(gsc_gender(2, Some(0.125)), Some(Gender::Male));
+        assert_eq!(gsc_gender(7, Some(0.5)), Some(Gender::Female));
+        assert_eq!(gsc_gender(15, Some(1.0)), Some(Gender::Female));
+        assert_eq!(gsc_gender(0, Some(0.0)), Some(Gender::Male));
+        assert_eq!(gsc_gender(0, None), None);
+
+        let attack = DvRange { min: 8, max: 15 };
+        assert!(verify_gsc_gender(Some(Gender::Male), &attack, Some(0.5)).is_ok());
+        assert!(verify_gsc_gender(Some(Gender::Female), &attack, Some(0.5)).is_err());
+    }
+
+    #[test]
+    fn shiny_from_dv_ranges() {
+        let ten = DvRange { min: 10, max: 10 };
+        let wide = DvRange { min: 8, max: 11 };
+        let low = DvRange { min: 0, max: 1 };
+
+        assert!(verify_gsc_shiny(true, &wide, &ten, &wide, &[&ten, &ten]).is_ok());
+        assert!(verify_gsc_shiny(true, &low, &ten, &ten, &[&ten, &ten]).is_err());
+        assert!(verify_gsc_shiny(false, &ten, &ten, &ten, &[&ten, &ten]).is_err());
+        assert!(verify_gsc_shiny(false, &wide, &ten, &ten, &[&ten, &ten]).is_ok());
+    }
 }
@@ -0,0 +1,230 @@
+//! Detection of text lines anywhere on the screen.
+//!
+//! The layouts read their fields from known positions. To find the texts of
+//! a new screen, every pixel position is tried as the top-left corner of a
+//! character, and the characters following each other are joined into lines.
+//! The lines need not be aligned to the tile grid.
+
+use crate::ocr::{CharBitmap, CharMatch, CharTable, MatchOptions};
+use crate::position::Position;
+use image::GrayImage;
+
+/// The distance between the characters of a line.
+const CHAR_STEP: u32 = 8;
+
+/// A line of text found on the screen.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextLine {
+    /// The position of the line, as expected by [`crate::ocr::read_field`].
+    pub pos: Position,
+    pub text: String,
+}
+
+/// The number of foreground pixels above and left of each pixel.
+///
+/// Tells whether a region is blank without visiting its pixels, as the
+/// detection checks the region of every pixel position.
+struct PixelCounts {
+    width: i64,
+    height: i64,
+    /// The counts of the (width + 1) x (height + 1) corners in row-major order.
+    sums: Vec<u32>,
+}
+
+impl PixelCounts {
+    fn new(img: &GrayImage) -> PixelCounts {
+        let (width, height) = (img.width() as usize, img.height() as usize);
+        let mut sums = vec![0; (width + 1) * (height + 1)];
+        for y in 0..height {
+            let mut row_sum = 0;
+            for x in 0..width {
+                row_sum += (img.get_pixel(x as u32, y as u32)[0] != 0) as u32;
+                sums[(y + 1) * (width + 1) + x + 1] = sums[y * (width + 1) + x + 1] + row_sum;
+            }
+        }
+        PixelCounts {
+            width: width as i64,
+            height: height as i64,
+            sums,
+        }
+    }
+
+    /// Returns true if the region has no foreground pixels.
+    ///
+    /// The pixels outside of the image are considered blank.
+    fn is_blank(&self, x: i64, y: i64, width: i64, height: i64) -> bool {
+        let (x0, x1) = (x.clamp(0, self.width), (x + width).clamp(0, self.width));
+        let (y0, y1) = (y.clamp(0, self.height), (y + height).clamp(0, self.height));
+        let sum = |x: i64, y: i64| self.sums[(y * (self.width + 1) + x) as usize];
+        sum(x1, y1) + sum(x0, y0) == sum(x0, y1) + sum(x1, y0)
+    }
+}
+
+/// Returns the character at the position, if any.
+///
+/// The characters of a line are separated by a single blank column, so
+/// the columns on both sides of the character must be blank. Tolerates a
+/// few differing pixels, see [`MatchOptions::default`].
+fn char_at(
+    img: &GrayImage,
+    counts: &PixelCounts,
+    x: u32,
+    y: u32,
+    chars: &CharTable,
+) -> Option<CharMatch> {
+    let (x_i, y_i) = (x as i64, y as i64);
+    if counts.is_blank(x_i, y_i, 7, 7) {
+        return None;
+    }
+    if !counts.is_blank(x_i - 1, y_i, 1, 7) || !counts.is_blank(x_i + 7, y_i, 1, 7) {
+        return None;
+    }
+
+    let pos = Position {
+        x,
+        y,
+        width: 7,
+        height: 7,
+    };
+    let bitmap = CharBitmap::from_image_region(img, &pos).ok()?;
+    chars.nearest(&bitmap, &MatchOptions::default()).ok()
+}
+
+/// Finds the lines of text on the binary image.
+///
+/// A line contains characters 8 pixels apart, with at most one space
+/// between its words. Lines without letters or digits are left out, as
+/// punctuation alone is easily mistaken for parts of sprites. So are lines
+/// of characters that only resemble their glyphs: noisy characters are read
+/// as part of a line with an exact one.
+pub fn detect_text(img: &GrayImage, chars: &CharTable) -> Vec<TextLine> {
+    let (w, h) = img.dimensions();
+    if w < 7 || h < 7 {
+        return Vec::new();
+    }
+
+    let counts = PixelCounts::new(img);
+    let mut hits: Vec<Vec<Option<CharMatch>>> = (0..=h - 7)
+        .map(|y| {
+            (0..=w - 7)
+                .map(|x| char_at(img, &counts, x, y, chars))
+                .collect()
+        })
+        .collect();
+
+    // A glyph found again a pixel lower is part of a longer shape, like a
+    // border. A glyph matching better a pixel higher or lower is the actual
+    // character, the other one is read off by a row.
+    let dropped: Vec<(usize, usize)> = (0..hits.len())
+        .flat_map(|y| (0..hits[y].len()).map(move |x| (x, y)))
+        .filter(|&(x, y)| {
+            let Some(hit) = &hits[y][x] else {
+                return false;
+            };
+            let neighbors = [y.checked_sub(1), Some(y + 1)];
+            neighbors
+                .into_iter()
+                .flatten()
+                .filter_map(|y| hits.get(y).and_then(|row| row[x].as_ref()))
+                .any(|other| other.char == hit.char || other.dist < hit.dist)
+        })
+        .collect();
+    for (x, y) in dropped {
+        hits[y][x] = None;
+    }
+
+    let mut lines = Vec::new();
+    for y in 0..=h - 7 {
+        for x in 0..=w - 7 {
+            if hits[y as usize][x as usize].is_none() {
+                continue;
+            }
+
+            // Follows the characters, allowing a single space between them
+            let mut text = String::new();
+            let mut char_count = 0;
+            let mut is_exact = false;
+            let mut next_x = x;
+            loop {
+                let hit = hits[y as usize]
+                    .get_mut(next_x as usize)
+                    .and_then(|x| x.take());
+                if let Some(hit) = hit {
+                    // Each char is part of a single line
+                    text.push_str(&hit.char);
+                    is_exact |= hit.dist == 0;
+                    char_count += 1;
+                    next_x += CHAR_STEP;
+                    continue;
+                }
+
+                let after_space = next_x + CHAR_STEP;
+                let is_space = next_x + 7 <= w && counts.is_blank(next_x as i64, y as i64, 7, 7);
+                let is_word = hits[y as usize]
+                    .get(after_space as usize)
+                    .is_some_and(|x| x.is_some());
+                if is_space && is_word {
+                    text.push(' ');
+                    char_count += 1;
+                    next_x = after_space;
+                    continue;
+                }
+                break;
+            }
+
+            if is_exact && text.chars().any(|c| c.is_alphanumeric()) {
+                lines.push(TextLine {
+                    pos: Position {
+                        x,
+                        y,
+                        width: char_count * CHAR_STEP - 1,
+                        height: 7,
+                    },
+                    text,
+                });
+            }
+        }
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ocr::read_field;
+    use crate::palette::normalize_palette;
+    use image::imageops::overlay;
+
+    const IMG: &[u8] = include_bytes!("../data/images/Crystal_summary_2.png");
+
+    #[test]
+    fn detect_shifted_lines() {
+        let img = image::load_from_memory(IMG).unwrap();
+        let img = normalize_palette(&img);
+        let chars = CharTable::new();
+
+        // Moves the screen off the tile grid
+        let mut shifted = GrayImage::new(170, 150);
+        overlay(&mut shifted, &img, 3, 2);
+
+        let lines = detect_text(&shifted, &chars);
+        let texts: Vec<&str> = lines.iter().map(|line| line.text.as_str()).collect();
+        assert!(texts.contains(&"TOTODILE"));
+        assert!(texts.contains(&"SCRATCH"));
+        assert!(texts.contains(&"35/35"));
+
+        let line = lines.iter().find(|line| line.text == "SCRATCH").unwrap();
+        assert_eq!((line.pos.x, line.pos.y), (67, 82));
+        assert_eq!(
+            read_field(&shifted, &line.pos, &chars),
+            Ok(line.text.clone())
+        );
+
+        // A flipped pixel of the "C" is tolerated
+        let pixel = shifted.get_pixel(77, 84)[0];
+        shifted.put_pixel(77, 84, image::Luma([255 - pixel]));
+        let lines = detect_text(&shifted, &chars);
+        assert!(lines.iter().any(|line| line.text == "SCRATCH"));
+    }
+}
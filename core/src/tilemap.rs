@@ -0,0 +1,244 @@
+//! Whole-screen OCR on the tile grid.
+//!
+//! The Game Boy renders the screen from 8x8 pixel tiles, the 160x144 screen
+//! is therefore a grid of 20x18 tiles. Text characters are always aligned to
+//! this grid, so decoding every tile turns the screen into text that can be
+//! queried by tile coordinates.
+
+use crate::ocr::{CharBitmap, CharMatch, CharTable, MatchOptions};
+use crate::position::Position;
+use crate::roi::Roi;
+use image::GrayImage;
+use std::fmt;
+
+/// The width and height of a tile in pixels.
+pub const TILE_SIZE: u32 = 8;
+
+/// The number of tile columns on the screen.
+pub const TILE_COLUMNS: u32 = 20;
+
+/// The number of tile rows on the screen.
+pub const TILE_ROWS: u32 = 18;
+
+/// Returns the position of a field of text, given by its first tile and
+/// number of characters.
+///
+/// The blank column after the last character is left out, as expected by
+/// [`crate::ocr::read_field`].
+pub fn tile_field(col: u32, row: u32, len: u32) -> Position {
+    Position {
+        x: col * TILE_SIZE,
+        y: row * TILE_SIZE,
+        width: len * TILE_SIZE - 1,
+        height: 7,
+    }
+}
+
+/// The character shown in place of unknown tiles when printing the tile map.
+pub const UNKNOWN_CHAR: char = '#';
+
+/// A decoded tile of the screen.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tile {
+    /// The recognized character, if any.
+    pub char: Option<String>,
+    /// The pixels of the tile as a bitmap in row-major order.
+    ///
+    /// Identifies the tiles that are not characters, such as borders or sprites.
+    pub hash: u64,
+    /// The confidence of the recognition, between 0 and 1.
+    pub confidence: f32,
+}
+
+impl Tile {
+    /// Decodes the tile at the given tile coordinates.
+    ///
+    /// A character occupies the top-left 7x7 pixels of the tile, but some,
+    /// like the slash of RBY, are drawn a pixel lower. The pixels outside of
+    /// the 7x7 region are ignored, except that a tile with any foreground
+    /// pixel is never a space. Noisy characters are matched to the closest
+    /// glyph, see [`CharTable::nearest`], if the rest of the tile is blank.
+    fn read(img: &GrayImage, col: u32, row: u32, chars: &CharTable) -> Result<Tile, String> {
+        let pos = Position {
+            x: col * TILE_SIZE,
+            y: row * TILE_SIZE,
+            width: TILE_SIZE,
+            height: TILE_SIZE,
+        };
+        let roi = Roi::new(img, pos)?;
+        let hash = roi
+            .iter()
+            .enumerate()
+            .map(|(idx, x)| ((*x != 0) as u64) << idx) // Shift left by idx
+            .fold(0, |acc, x| acc | x); // Bitwise OR
+
+        let first_row: u64 = 0x0000_0000_0000_00FF;
+        let last_row: u64 = 0xFF00_0000_0000_0000;
+        let last_col: u64 = 0x8080_8080_8080_8080;
+
+        // The vertical offsets with the pixels outside of the character.
+        // Descenders, like of "g", reach the last row.
+        let mut offsets = vec![(0, last_row | last_col)];
+        if hash & first_row == 0 {
+            offsets.push((1, first_row | last_col));
+        }
+
+        let options = MatchOptions::default();
+        let mut best: Option<CharMatch> = None;
+        for (offset_y, outside) in offsets {
+            let char_pos = Position {
+                x: pos.x,
+                y: pos.y + offset_y,
+                width: 7,
+                height: 7,
+            };
+            let bitmap = CharBitmap::from_image_region(img, &char_pos)?;
+            let Ok(char) = chars.nearest(&bitmap, &options) else {
+                continue;
+            };
+            if char.char == " " && hash != 0 {
+                continue;
+            }
+            if char.dist > 0 && hash & outside != 0 {
+                continue; // Only isolated characters may be noisy
+            }
+            let is_better = match &best {
+                Some(best) => char.dist < best.dist,
+                None => true,
+            };
+            if is_better {
+                best = Some(char);
+            }
+        }
+
+        if let Some(char) = best {
+            return Ok(Tile {
+                char: Some(char.char),
+                hash,
+                confidence: char.confidence,
+            });
+        }
+
+        Ok(Tile {
+            char: None,
+            hash,
+            confidence: 0.0,
+        })
+    }
+}
+
+/// The decoded 20x18 tiles of the screen.
+pub struct TileMap {
+    tiles: Vec<Tile>,
+}
+
+impl TileMap {
+    /// Decodes every tile of the screen.
+    ///
+    /// Expects the image to be a 160x144 binary image.
+    pub fn read(img: &GrayImage, chars: &CharTable) -> Result<TileMap, String> {
+        let (w, h) = img.dimensions();
+        if w != TILE_COLUMNS * TILE_SIZE || h != TILE_ROWS * TILE_SIZE {
+            return Err(format!("expected image of 160x144 pixels, got {w}x{h}"));
+        }
+
+        let mut tiles = Vec::with_capacity((TILE_COLUMNS * TILE_ROWS) as usize);
+        for row in 0..TILE_ROWS {
+            for col in 0..TILE_COLUMNS {
+                tiles.push(Tile::read(img, col, row, chars)?);
+            }
+        }
+
+        Ok(TileMap { tiles })
+    }
+
+    /// Returns the tile at the tile coordinates.
+    pub fn get(&self, col: u32, row: u32) -> Option<&Tile> {
+        if col >= TILE_COLUMNS || row >= TILE_ROWS {
+            return None;
+        }
+        self.tiles.get((row * TILE_COLUMNS + col) as usize)
+    }
+
+    /// Returns the text of `len` tiles, starting from the tile coordinates.
+    ///
+    /// Unknown tiles are replaced by [`UNKNOWN_CHAR`].
+    pub fn text(&self, col: u32, row: u32, len: u32) -> String {
+        (col..col + len)
+            .filter_map(|c| self.get(c, row))
+            .map(|tile| tile.char.clone().unwrap_or(UNKNOWN_CHAR.to_string()))
+            .collect()
+    }
+
+    /// Returns the text of a row of tiles.
+    pub fn row_text(&self, row: u32) -> String {
+        self.text(0, row, TILE_COLUMNS)
+    }
+
+    /// Returns the lowest confidence among `len` tiles, starting from the tile coordinates.
+    pub fn confidence(&self, col: u32, row: u32, len: u32) -> f32 {
+        (col..col + len)
+            .filter_map(|c| self.get(c, row))
+            .map(|tile| tile.confidence)
+            .fold(1.0, f32::min)
+    }
+
+    /// Returns the tile coordinates of the first occurrence of the text.
+    pub fn find(&self, text: &str) -> Option<(u32, u32)> {
+        (0..TILE_ROWS).find_map(|row| {
+            let row_text: Vec<String> = (0..TILE_COLUMNS)
+                .map(|col| self.text(col, row, 1))
+                .collect();
+            (0..TILE_COLUMNS as usize)
+                .find(|&col| row_text[col..].concat().starts_with(text))
+                .map(|col| (col as u32, row))
+        })
+    }
+
+    /// Returns the distinct hashes of the unknown tiles.
+    pub fn unknown_hashes(&self) -> Vec<u64> {
+        let mut hashes: Vec<u64> = Vec::new();
+        for tile in &self.tiles {
+            if tile.char.is_none() && !hashes.contains(&tile.hash) {
+                hashes.push(tile.hash);
+            }
+        }
+        hashes
+    }
+}
+
+impl fmt::Display for TileMap {
+    /// Prints the screen as text, one line per tile row.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in 0..TILE_ROWS {
+            writeln!(f, "{}", self.row_text(row))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::palette::normalize_palette;
+
+    const IMG: &[u8] = include_bytes!("../data/images/Yellow_summary_1.png");
+
+    #[test]
+    fn read_summary_screen() {
+        let img = image::load_from_memory(IMG).unwrap();
+        let img = normalize_palette(&img);
+        let chars = CharTable::new();
+
+        let tilemap = TileMap::read(&img, &chars).unwrap();
+
+        assert_eq!(tilemap.text(9, 1, 7), "PIKACHU");
+        assert_eq!(tilemap.text(3, 7, 3), "025");
+        assert_eq!(tilemap.text(13, 4, 6), "20/ 20");
+        assert_eq!(tilemap.find("PIKACHU"), Some((9, 1)));
+        assert_eq!(tilemap.confidence(9, 1, 7), 1.0);
+        assert_eq!(tilemap.get(1, 7).unwrap().char, None); // The "No" ligature
+        assert_eq!(tilemap.text(2, 7, 1), ".");
+        assert_eq!(tilemap.to_string().lines().count(), TILE_ROWS as usize);
+    }
+}
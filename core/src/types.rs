@@ -0,0 +1,127 @@
+//! The effectiveness of the types against each other.
+//!
+//! The type chart of RBY differs from the one of GSC: Dark and Steel are
+//! missing, and a few matchups changed, like Ghost not affecting Psychic.
+
+use std::collections::HashMap;
+
+#[derive(serde::Deserialize)]
+struct Matchup {
+    attacking: String,
+    defending: String,
+    /// Empty if one of the types is not in RBY.
+    rby: Option<f32>,
+    gsc: f32,
+}
+
+/// The damage multipliers of the types of a generation.
+pub struct TypeChart {
+    types: Vec<String>,
+    multipliers: HashMap<(String, String), f32>,
+}
+
+impl TypeChart {
+    /// Creates the type chart of RBY.
+    pub fn rby() -> TypeChart {
+        TypeChart::from_matchups(|matchup| matchup.rby)
+    }
+
+    /// Creates the type chart of GSC.
+    pub fn gsc() -> TypeChart {
+        TypeChart::from_matchups(|matchup| Some(matchup.gsc))
+    }
+
+    /// Creates a type chart from the multipliers picked from the matchups.
+    fn from_matchups(multiplier: impl Fn(&Matchup) -> Option<f32>) -> TypeChart {
+        let mut types = Vec::new();
+        let mut multipliers = HashMap::new();
+
+        // Only the matchups that are not neutral in both generations are listed
+        const CSV_DATA: &str = include_str!("../data/type_chart.csv");
+        let mut csv_reader = csv::ReaderBuilder::new()
+            .has_headers(true)
+            .from_reader(CSV_DATA.as_bytes());
+
+        for result in csv_reader.deserialize() {
+            let matchup: Matchup = result.expect("could not deserialize type matchup");
+            let Some(multiplier) = multiplier(&matchup) else {
+                continue;
+            };
+            if !types.contains(&matchup.attacking) {
+                types.push(matchup.attacking.clone());
+            }
+            multipliers.insert((matchup.attacking, matchup.defending), multiplier);
+        }
+
+        TypeChart { types, multipliers }
+    }
+
+    /// Returns the types of the generation.
+    pub fn types(&self) -> &[String] {
+        &self.types
+    }
+
+    /// Returns the damage multiplier of the attacking type against the types.
+    ///
+    /// The second type is empty for Pokémon of a single type.
+    pub fn multiplier(&self, attacking: &str, type1: &str, type2: &str) -> f32 {
+        [type1, type2]
+            .iter()
+            .filter(|x| !x.is_empty())
+            .map(|defending| {
+                let key = (attacking.to_string(), defending.to_string());
+                self.multipliers.get(&key).copied().unwrap_or(1.0)
+            })
+            .product()
+    }
+
+    /// Returns the types that are super effective against the types, with
+    /// their multiplier, the most effective first.
+    pub fn weaknesses(&self, type1: &str, type2: &str) -> Vec<(&str, f32)> {
+        let mut weaknesses: Vec<(&str, f32)> = self
+            .types
+            .iter()
+            .map(|x| (x.as_str(), self.multiplier(x, type1, type2)))
+            .filter(|(_, multiplier)| *multiplier > 1.0)
+            .collect();
+        weaknesses.sort_by(|a, b| b.1.total_cmp(&a.1));
+        weaknesses
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weaknesses_per_generation() {
+        let rby = TypeChart::rby();
+        let gsc = TypeChart::gsc();
+        assert_eq!(rby.types().len(), 15);
+        assert_eq!(gsc.types().len(), 17);
+
+        assert_eq!(rby.multiplier("Ghost", "Psychic", ""), 0.0);
+        assert_eq!(gsc.multiplier("Ghost", "Psychic", ""), 2.0);
+        assert_eq!(gsc.multiplier("Ice", "Grass", "Flying"), 4.0);
+
+        assert_eq!(
+            gsc.weaknesses("Grass", "Poison"),
+            [
+                ("Fire", 2.0),
+                ("Ice", 2.0),
+                ("Flying", 2.0),
+                ("Psychic", 2.0)
+            ]
+        );
+        assert_eq!(
+            rby.weaknesses("Grass", "Poison"),
+            [
+                ("Bug", 4.0),
+                ("Fire", 2.0),
+                ("Ice", 2.0),
+                ("Flying", 2.0),
+                ("Psychic", 2.0)
+            ]
+        );
+    }
+}
@@ -1,23 +1,53 @@
 //! High-level functions to scan the game screens and get a printable results.
 
 use crate as pkmn;
+use crate::calibration::Calibration;
+use crate::correction::Dictionary;
 use crate::fmt;
-use crate::gameboy::{GscSummary1, GscSummary2, GscSummary3, RbySummary1, RbySummary2};
-use crate::items::GscItems;
-use crate::learnset::{GscLearnsets, RbyLearnsets};
-use crate::moves::{GscMoves, Moves};
+use crate::gameboy::{
+    AspectModel, BagContent, BagItem, BattleContent, BoxEntry, FightMenuContent, GscBadgePage,
+    GscBag, GscBattle, GscCardPage, GscFightMenu, GscLevelUp, GscParty, GscPokedexEntry,
+    GscSummary1, GscSummary2, GscSummary3, GscTrainerCard, PartySlot, PokedexEntryContent, RbyBag,
+    RbyBattle, RbyFightMenu, RbyLevelUp, RbyParty, RbyPokedexEntry, RbySummary1, RbySummary2,
+    RbyTrainerCard, TrainerCardContent,
+};
+use crate::items::{GscItems, RbyItems, TmHms};
+use crate::learnset::{GscLearnsets, Learnset, RbyLearnsets};
+use crate::moves::{GscMoves, Move, Moves};
 use crate::ocr::CharTable;
-use crate::pokemon::{GscPokedex, RbyPokedex};
-use crate::stats::{DvRange, StatVariation};
-use image::imageops::invert;
+use crate::pc::BoxInventory;
+use crate::pokemon::{ingame_name, GscPokedex, RbyPokedex};
+use crate::position::Position;
+use crate::screen::ScreenKind;
+use crate::session::ScanSession;
+use crate::stats::{
+    badge_boost, hp_dv, verify_gsc_gender, verify_gsc_shiny, DvRange, StatVariation, MAX_STAT_EXP,
+    RBY_BADGE_BOOSTS, TRAINER_DVS,
+};
+use crate::types::TypeChart;
 use image::{DynamicImage, GrayImage};
-use imageproc::contrast::threshold_mut;
+
+/// Snaps a misread name to the closest valid one.
+///
+/// The corrections are noted to be shown along the results.
+fn correct_name(name: &str, dictionary: &Dictionary, notes: &mut String) -> String {
+    match dictionary.correct(name) {
+        Some(correction) => {
+            if correction.is_corrected() {
+                notes.push_str(&fmt::fmt_correction(&correction));
+            }
+            correction.name
+        }
+        None => name.to_string(),
+    }
+}
 
 fn scan_rby_summary_1(
     img_gameboy: &GrayImage,
     rby_summary_1: &RbySummary1,
     chars: &CharTable,
     rby_pokedex: &RbyPokedex,
+    session: &mut ScanSession,
 ) -> Result<String, String> {
     let content = rby_summary_1
         .read_fields(&img_gameboy, &chars)
@@ -27,6 +57,9 @@ fn scan_rby_summary_1(
     let pokemon = rby_pokedex
         .get_ndex(ndex)
         .ok_or(format!("could not find Pokemon with ndex '{ndex}'"))?;
+    if let Some(nickname) = &content.nickname {
+        session.record_species(nickname, &pokemon.name);
+    }
 
     let var_hp = StatVariation::init(&content.level, &pokemon.hp, &0, &true);
     let var_attack = StatVariation::init(&content.level, &pokemon.attack, &0, &false);
@@ -127,6 +160,21 @@ fn scan_rby_summary_1(
         ));
     }
 
+    t.push_str(&fmt_traded(session, content.ot.as_deref(), content.id));
+    t.push_str(&fmt_badge_boosts(
+        &[
+            ("ATT", "Attack", content.attack),
+            ("DEF", "Defense", content.defense),
+            ("SPD", "Speed", content.speed),
+            ("SPC", "Special", content.special),
+        ],
+        session.badges(),
+    ));
+
+    if let Err(err) = content.verify_types(pokemon) {
+        t.push_str(&format!("\nCheck failed: {err}\n"));
+    }
+
     return Ok(t);
 }
 
@@ -180,9 +228,30 @@ fn scan_rby_summary_2(
         .collect::<Result<Vec<_>, _>>()
         .map_err(|err| format!("could not collect learnsets for evo chain: {err}"))?;
 
+    let pokemon = rby_pokedex
+        .get_ndex(ndex)
+        .ok_or(&format!("could not find Pokemon at ndex '{ndex}'"))?;
+    let level = content.level();
+
     let mut text_result = String::with_capacity(256);
 
-    text_result.push_str(&format!("No.{} {}\n\n", ndex, pkmn_name,));
+    text_result.push_str(&format!("No.{} {} :L{}\n", ndex, pkmn_name, level));
+    text_result.push_str(&fmt::fmt_exp_progress(
+        level,
+        content.exp,
+        content.exp_to_next,
+        &pokemon.growth_rate,
+    ));
+    if let Err(err) = pokemon
+        .growth_rate
+        .verify_exp(level, content.exp, content.exp_to_next)
+    {
+        text_result.push_str(&format!("Check failed: {err}\n"));
+    }
+    text_result.push('\n');
+
+    let move_names = Dictionary::new(rby_moves.names(), chars);
+    let mut corrections = String::new();
 
     text_result.push_str(&format!("{}\n", &fmt::fmt_move_header()));
     for move_name in [
@@ -194,11 +263,22 @@ fn scan_rby_summary_2(
         match move_name.as_str() {
             "-" => text_result.push_str("-\n"),
             _ => {
+                let move_name = correct_name(move_name, &move_names, &mut corrections);
                 let move_ = rby_moves.get(&move_name);
                 text_result.push_str(&format!("{}\n", fmt::fmt_move(move_)));
             }
         }
     }
+    text_result.push_str(&corrections);
+
+    let next_moves = rby_learnsets
+        .get_pokemon(&pkmn_name)
+        .map(|learnset| learnset.next_moves(level))
+        .unwrap_or_default();
+    if !next_moves.is_empty() {
+        text_result.push_str("\nNext level-up move(s)\n");
+        text_result.push_str(&fmt::fmt_level_up_moves(&next_moves));
+    }
 
     text_result.push_str(&"\nEvo chain(s)\n");
     println!("Evo chains\n");
@@ -222,46 +302,26 @@ fn scan_gsc_summary_1(
     gsc_summary_1: &GscSummary1,
     chars: &CharTable,
     gsc_pokedex: &GscPokedex,
+    session: &mut ScanSession,
 ) -> Result<String, String> {
-    let ndex = pkmn::ocr::read_field(&img_gameboy, &gsc_summary_1.ndex, &chars);
-
-    let level = pkmn::ocr::read_field(&img_gameboy, &gsc_summary_1.level, &chars);
-
-    let hp = pkmn::ocr::read_field(&img_gameboy, &gsc_summary_1.hp, &chars);
+    let content = gsc_summary_1
+        .read_fields(img_gameboy, chars)
+        .map_err(|err| format!("could not read GSC summary 1: {err}"))?;
 
     let mut t: String = String::new();
     #[cfg(debug_assertions)]
-    {
-        t.push_str("GSC Summary 1\n");
-        t.push_str(&format!("Ndex: {ndex:?}\n"));
-        t.push_str(&format!("Level: {level:?}\n"));
-        t.push_str(&format!("Hp: {hp:?}\n"));
-    }
-
-    let ndex = pkmn::ocr::read_field(&img_gameboy, &gsc_summary_1.ndex, &chars)
-        .map_err(|err| format!("could not read ndex: {err}"))?;
-    let ndex = ndex
-        .trim()
-        .parse::<usize>()
-        .map_err(|_| format!("could not parse ndex '{ndex}' to an integer"))?;
-
-    let level = pkmn::ocr::read_field(&img_gameboy, &gsc_summary_1.level, &chars)
-        .map_err(|err| format!("could not read level: {err}"))?;
-    let level = level
-        .trim()
-        .parse::<i32>()
-        .map_err(|_| format!("could not parse level '{level}' to an integer"))?;
-
-    let hp = pkmn::ocr::read_field(&img_gameboy, &gsc_summary_1.hp, &chars)
-        .map_err(|err| format!("could not read hp: {err}"))?;
-    let hp = hp
-        .trim()
-        .parse::<i32>()
-        .map_err(|_| format!("could not parse hp '{hp}' to an integer"))?;
+    t.push_str(&format!("GSC Summary 1\n{content:?}\n"));
+
+    let ndex = content.ndex as usize;
+    let level = content.level;
+    let hp = content.hp;
 
     let pokemon = gsc_pokedex
         .get_ndex(ndex)
         .ok_or(format!("could not find Pokemon at ndex '{ndex}'"))?;
+    if let Some(nickname) = &content.nickname {
+        session.record_species(nickname, &pokemon.name);
+    }
 
     let var_hp = StatVariation::init(&level, &pokemon.hp, &0, &true);
     let range_hp = DvRange::init(&hp, &var_hp)
@@ -304,70 +364,21 @@ fn scan_gsc_summary_2(
     gsc_evo_chains: &Vec<String>,
     gsc_learnsets: &GscLearnsets,
 ) -> Result<String, String> {
-    let ndex = pkmn::ocr::read_field(&img_gameboy, &gsc_summary_2.ndex, &chars);
-
-    let level = pkmn::ocr::read_field(&img_gameboy, &gsc_summary_2.level, &chars);
-
-    let item = pkmn::ocr::read_field(&img_gameboy, &gsc_summary_2.item, &chars);
-
-    let move_1 = pkmn::ocr::read_field(&img_gameboy, &gsc_summary_2.move_1, &chars);
-
-    let move_2 = pkmn::ocr::read_field(&img_gameboy, &gsc_summary_2.move_2, &chars);
-
-    let move_3 = pkmn::ocr::read_field(&img_gameboy, &gsc_summary_2.move_3, &chars);
-
-    let move_4 = pkmn::ocr::read_field(&img_gameboy, &gsc_summary_2.move_4, &chars);
+    let content = gsc_summary_2
+        .read_fields(img_gameboy, chars)
+        .map_err(|err| format!("could not read GSC summary 2: {err}"))?;
 
     let mut t = String::new();
-
     #[cfg(debug_assertions)]
-    {
-        t.push_str("GSC Summary 2\n");
-        t.push_str(&format!("Ndex: {ndex:?}\n"));
-        t.push_str(&format!("Level: {level:?}\n"));
-        t.push_str(&format!("Item: {item:?}\n"));
-        t.push_str(&format!("Move 1: {move_1:?}\n"));
-        t.push_str(&format!("Move 2: {move_2:?}\n"));
-        t.push_str(&format!("Move 3: {move_3:?}\n"));
-        t.push_str(&format!("Move 4: {move_4:?}\n"));
-    }
-
-    let ndex = ndex.map_err(|err| format!("could not read ndex: {err}"))?;
-    let ndex = ndex
-        .trim()
-        .parse::<usize>()
-        .map_err(|_| format!("could not parse ndex '{ndex}' to an integer"))?;
-
-    let level = level.map_err(|err| format!("could not read level: {err}"))?;
-    let level = level
-        .trim()
-        .parse::<i32>()
-        .map_err(|_| format!("could not parse level '{level}' to an integer"))?;
-
-    let item_name = item
-        .map_err(|err| format!("could not read item: {err}"))?
-        .trim()
-        .to_string();
-
-    let move_1 = move_1
-        .map_err(|err| format!("could not read move_1: {err}"))?
-        .trim()
-        .to_string();
-
-    let move_2 = move_2
-        .map_err(|err| format!("could not read move_2: {err}"))?
-        .trim()
-        .to_string();
-
-    let move_3 = move_3
-        .map_err(|err| format!("could not read move_3: {err}"))?
-        .trim()
-        .to_string();
-
-    let move_4 = move_4
-        .map_err(|err| format!("could not read move_4: {err}"))?
-        .trim()
-        .to_string();
+    t.push_str(&format!("GSC Summary 2\n{content:?}\n"));
+
+    let ndex = content.ndex as usize;
+    let level = content.level;
+    let item_name = content.item;
+    let move_1 = content.move_1;
+    let move_2 = content.move_2;
+    let move_3 = content.move_3;
+    let move_4 = content.move_4;
 
     let pokemon = &gsc_pokedex
         .get_ndex(ndex)
@@ -377,23 +388,36 @@ fn scan_gsc_summary_2(
         pokemon.ndex, pokemon.name, level
     ));
 
+    let item_names = Dictionary::new(gsc_items.names(), chars);
+    let move_names = Dictionary::new(gsc_moves.names(), chars);
+    let mut corrections = String::new();
+
     t.push_str(&format!("Item\n"));
-    let item = gsc_items.get(&item_name);
-    match item {
-        Some(item) => t.push_str(&format!("{:<12}  {}\n\n", item_name, item.description)),
-        None => t.push_str(&format!("{:<12}  {}\n\n", item_name, "NO DATA")),
+    match item_name {
+        Some(item_name) => {
+            let item_name = correct_name(&item_name, &item_names, &mut corrections);
+            let item = gsc_items.get(&item_name);
+            match item {
+                Some(item) => t.push_str(&format!("{:<12}  {}\n\n", item_name, item.description)),
+                None => t.push_str(&format!("{:<12}  {}\n\n", item_name, "NO DATA")),
+            }
+        }
+        None => t.push_str("?\n\n"),
     }
 
     t.push_str(&format!("{}\n", &fmt::fmt_move_header()));
     for move_name in [&move_1, &move_2, &move_3, &move_4] {
-        match move_name.as_str() {
-            "-" => t.push_str("-\n"),
-            _ => {
+        match move_name.as_deref() {
+            None => t.push_str("?\n"),
+            Some("-") => t.push_str("-\n"),
+            Some(move_name) => {
+                let move_name = correct_name(move_name, &move_names, &mut corrections);
                 let move_ = gsc_moves.get(&move_name);
                 t.push_str(&format!("{}\n", fmt::fmt_move(move_)));
             }
         }
     }
+    t.push_str(&corrections);
 
     let pokemon = &gsc_pokedex
         .get_ndex(ndex)
@@ -444,76 +468,23 @@ fn scan_gsc_summary_3(
     gsc_summary_3: &GscSummary3,
     chars: &CharTable,
     gsc_pokedex: &GscPokedex,
+    session: &ScanSession,
 ) -> Result<String, String> {
-    let ndex = pkmn::ocr::read_field(&img_gameboy, &gsc_summary_3.ndex, &chars);
-
-    let level = pkmn::ocr::read_field(&img_gameboy, &gsc_summary_3.level, &chars);
-
-    let attack = pkmn::ocr::read_field(&img_gameboy, &gsc_summary_3.attack, &chars);
-
-    let defense = pkmn::ocr::read_field(&img_gameboy, &gsc_summary_3.defense, &chars);
-
-    let spc_attack = pkmn::ocr::read_field(&img_gameboy, &gsc_summary_3.spc_attack, &chars);
-
-    let spc_defense = pkmn::ocr::read_field(&img_gameboy, &gsc_summary_3.spc_defense, &chars);
-
-    let speed = pkmn::ocr::read_field(&img_gameboy, &gsc_summary_3.speed, &chars);
+    let content = gsc_summary_3
+        .read_fields(img_gameboy, chars)
+        .map_err(|err| format!("could not read GSC summary 3: {err}"))?;
 
     let mut t = String::new();
-
     #[cfg(debug_assertions)]
-    {
-        t.push_str("GSC Summary 3\n");
-        t.push_str(&format!("Ndex: {ndex:?}\n"));
-        t.push_str(&format!("Level: {level:?}\n"));
-        t.push_str(&format!("Attack: {attack:?}\n"));
-        t.push_str(&format!("Defense: {defense:?}\n"));
-        t.push_str(&format!("Spc. Attack: {spc_attack:?}\n"));
-        t.push_str(&format!("Spc. Defense: {spc_defense:?}\n"));
-        t.push_str(&format!("Speed: {speed:?}\n"));
-    }
-
-    let ndex = ndex.map_err(|err| format!("could not read ndex: {err}"))?;
-    let ndex = ndex
-        .trim()
-        .parse::<usize>()
-        .map_err(|_| format!("could not parse ndex '{ndex}' to an integer"))?;
-
-    let level = level.map_err(|err| format!("could not read level: {err}"))?;
-    let level = level
-        .trim()
-        .parse::<i32>()
-        .map_err(|_| format!("could not parse level '{level}' to an integer"))?;
-
-    let attack = attack.map_err(|err| format!("could not read attack: {err}"))?;
-    let attack = attack
-        .trim()
-        .parse::<i32>()
-        .map_err(|_| format!("could not parse attack '{attack}' to an integer"))?;
-
-    let defense = defense.map_err(|err| format!("could not read defense: {err}"))?;
-    let defense = defense
-        .trim()
-        .parse::<i32>()
-        .map_err(|_| format!("could not parse defense '{defense}' to an integer"))?;
-
-    let spc_attack = spc_attack.map_err(|err| format!("could not read spc_attack: {err}"))?;
-    let spc_attack = spc_attack
-        .trim()
-        .parse::<i32>()
-        .map_err(|_| format!("could not parse spc_attack '{spc_attack}' to an integer"))?;
-
-    let spc_defense = spc_defense.map_err(|err| format!("could not read spc_defense: {err}"))?;
-    let spc_defense = spc_defense
-        .trim()
-        .parse::<i32>()
-        .map_err(|_| format!("could not parse spc_defense '{spc_defense}' to an integer"))?;
-
-    let speed = speed.map_err(|err| format!("could not read speed: {err}"))?;
-    let speed = speed
-        .trim()
-        .parse::<i32>()
-        .map_err(|_| format!("could not parse speed '{speed}' to an integer"))?;
+    t.push_str(&format!("GSC Summary 3\n{content:?}\n"));
+
+    let ndex = content.ndex as usize;
+    let level = content.level;
+    let attack = content.attack;
+    let defense = content.defense;
+    let spc_attack = content.spc_attack;
+    let spc_defense = content.spc_defense;
+    let speed = content.speed;
 
     let pokemon = &gsc_pokedex
         .get_ndex(ndex)
@@ -614,15 +585,991 @@ fn scan_gsc_summary_3(
         ));
     }
 
+    let checks = [
+        // No symbol is shown for the genderless Pokémon, and it may be unreadable
+        content.gender.map_or(Ok(()), |gender| {
+            verify_gsc_gender(Some(gender), &range_attack, pokemon.female_ratio)
+        }),
+        verify_gsc_shiny(
+            content.shiny,
+            &range_attack,
+            &range_defense,
+            &range_speed,
+            &[&range_spc_attack, &range_spc_defense],
+        ),
+    ];
+    t.push_str(&fmt_traded(session, content.ot.as_deref(), content.id));
+    for err in checks.into_iter().filter_map(|x| x.err()) {
+        t.push_str(&format!("\nCheck failed: {err}\n"));
+    }
+
     return Ok(t);
 }
 
-/// Locates and reads the game screen and returns the details of the Pokemon.
+/// Returns the types of a Pokémon, e.g. "Normal/Flying".
+fn fmt_types(type1: &str, type2: &str) -> String {
+    match type2 {
+        "" => type1.to_string(),
+        _ => format!("{type1}/{type2}"),
+    }
+}
+
+/// Returns the line of the Pokémon of the player in battle.
+fn fmt_player_side(content: &BattleContent) -> String {
+    let player = &content.player;
+    let level = player.level.map_or("?".to_string(), |x| x.to_string());
+    format!(
+        "Your {} :L{}  HP {}/{}  {}\n",
+        player.name, level, content.hp, content.max_hp, player.status
+    )
+}
+
+/// The species of the opponent, as shown in the battle report.
+struct BattleSpecies<'a> {
+    /// E.g. "No.16 Pidgey", followed by the gender symbol in GSC.
+    title: String,
+    type1: &'a str,
+    type2: &'a str,
+    /// The stats by name, base stat and the DV of trainers, if known.
+    ///
+    /// The HP comes first.
+    stats: Vec<(&'static str, i32, Option<i32>)>,
+    learnset: Option<&'a Learnset>,
+}
+
+/// Returns the report of the battle screen, the same in RBY and GSC.
 ///
-/// A one-stop function to locate the game on the input image, to read its
-/// content and to return the relevant info in a human readable form.
-/// Works with the summary screens of RBY and GSC.
-pub fn scan_img(img_screen: DynamicImage) -> Result<String, String> {
+/// The opponent's types, weaknesses, HP, stats and last level-up moves are
+/// followed by the line of the player's Pokémon.
+fn fmt_battle(
+    content: &BattleContent,
+    species: &BattleSpecies,
+    types: &TypeChart,
+    corrections: &str,
+) -> Result<String, String> {
+    let opponent = &content.opponent;
+    let header = match opponent.level {
+        Some(level) => format!("Enemy {} :L{level}\n", species.title),
+        None => format!("Enemy {} ({})\n", species.title, opponent.status),
+    };
+    let mut t = header;
+    t.push_str(&format!(
+        "Type: {}\n",
+        fmt_types(species.type1, species.type2)
+    ));
+    t.push_str(&fmt::fmt_weaknesses(
+        &types.weaknesses(species.type1, species.type2),
+    ));
+
+    let Some(level) = opponent.level else {
+        t.push_str("\nLevel hidden by the status condition\n");
+        t.push_str(corrections);
+        t.push('\n');
+        t.push_str(&fmt_player_side(content));
+        return Ok(t);
+    };
+
+    let base_hp = species.stats[0].1;
+    let var_hp = opponent.max_hp(base_hp)?;
+    match opponent.hp(base_hp) {
+        Ok(hp) => t.push_str(&format!(
+            "HP: {} - {} of {} - {}\n",
+            hp.min,
+            hp.max,
+            var_hp.range().min,
+            var_hp.range().max
+        )),
+        Err(err) => t.push_str(&format!("Check failed: {err}\n")),
+    }
+    t.push_str(corrections);
+
+    let has_trainer_dvs = species.stats.iter().all(|(_, _, dv)| dv.is_some());
+    t.push('\n');
+    t.push_str(&fmt::fmt_battle_stat_header(has_trainer_dvs));
+    for (stat, base, trainer_dv) in &species.stats {
+        let variation = StatVariation::init(&level, base, &0, &(*stat == "HP"));
+        t.push_str(&fmt::fmt_battle_stat_row(
+            stat,
+            base,
+            &variation,
+            *trainer_dv,
+        ));
+    }
+
+    let last_moves = species
+        .learnset
+        .map(|learnset| learnset.last_moves(level))
+        .unwrap_or_default();
+    if !last_moves.is_empty() {
+        t.push_str("\nLevel-up moves\n");
+        t.push_str(&fmt::fmt_level_up_moves(&last_moves));
+    }
+
+    t.push('\n');
+    t.push_str(&fmt_player_side(content));
+    Ok(t)
+}
+
+fn scan_rby_battle(
+    img_gameboy: &GrayImage,
+    rby_battle: &RbyBattle,
+    chars: &CharTable,
+    rby_pokedex: &RbyPokedex,
+    rby_learnsets: &RbyLearnsets,
+    rby_types: &TypeChart,
+) -> Result<String, String> {
+    let content = rby_battle
+        .read_fields(img_gameboy, chars)
+        .map_err(|err| format!("could not read RBY battle: {err}"))?;
+
+    let names = rby_pokedex.ingame_names();
+    let names = Dictionary::new(names.iter().map(|x| x.as_str()), chars);
+    let mut corrections = String::new();
+    let name = correct_name(&content.opponent.name, &names, &mut corrections);
+    let pokemon = rby_pokedex
+        .get_ingame(&name)
+        .ok_or(format!("could not find Pokemon '{name}'"))?;
+
+    // All trainers of RBY share the DVs
+    let [attack_dv, defense_dv, speed_dv, special_dv] = TRAINER_DVS;
+    let hp_dv = hp_dv(attack_dv, defense_dv, speed_dv, special_dv);
+    let species = BattleSpecies {
+        title: format!("No.{} {}", pokemon.ndex, pokemon.name),
+        type1: &pokemon.type1,
+        type2: &pokemon.type2,
+        stats: vec![
+            ("HP", pokemon.hp, Some(hp_dv)),
+            ("ATT", pokemon.attack, Some(attack_dv)),
+            ("DEF", pokemon.defense, Some(defense_dv)),
+            ("SPD", pokemon.speed, Some(speed_dv)),
+            ("SPC", pokemon.special, Some(special_dv)),
+        ],
+        learnset: rby_learnsets.get_pokemon(&pokemon.name),
+    };
+    fmt_battle(&content, &species, rby_types, &corrections)
+}
+
+fn scan_gsc_battle(
+    img_gameboy: &GrayImage,
+    gsc_battle: &GscBattle,
+    chars: &CharTable,
+    gsc_pokedex: &GscPokedex,
+    gsc_learnsets: &GscLearnsets,
+    gsc_types: &TypeChart,
+) -> Result<String, String> {
+    let content = gsc_battle
+        .read_fields(img_gameboy, chars)
+        .map_err(|err| format!("could not read GSC battle: {err}"))?;
+
+    let names = gsc_pokedex.ingame_names();
+    let names = Dictionary::new(names.iter().map(|x| x.as_str()), chars);
+    let mut corrections = String::new();
+    let name = correct_name(&content.opponent.name, &names, &mut corrections);
+    let pokemon = gsc_pokedex
+        .get_ingame(&name)
+        .ok_or(format!("could not find Pokemon '{name}'"))?;
+    let gender = content.opponent.gender.map_or("", |x| x.symbol());
+
+    // The DVs of trainers differ by trainer class in GSC, so they are left out
+    let species = BattleSpecies {
+        title: format!("No.{} {}{gender}", pokemon.ndex, pokemon.name),
+        type1: &pokemon.type1,
+        type2: &pokemon.type2,
+        stats: vec![
+            ("HP", pokemon.hp, None),
+            ("ATT", pokemon.attack, None),
+            ("DEF", pokemon.defense, None),
+            ("SPA", pokemon.special_attack, None),
+            ("SPD", pokemon.special_defense, None),
+            ("SPE", pokemon.speed, None),
+        ],
+        learnset: gsc_learnsets.get_pokemon(&pokemon.name),
+    };
+    fmt_battle(&content, &species, gsc_types, &corrections)
+}
+
+/// Returns the PP of the move under the cursor and the PP Ups used on it.
+///
+/// The type shown in the menu is checked against the move data.
+fn fmt_fight_menu_pp(content: &FightMenuContent, move_: Option<&Move>) -> String {
+    let mut t = format!(
+        "{}  PP {}/{}\n",
+        content.selected_move(),
+        content.pp,
+        content.max_pp
+    );
+    let Some(move_) = move_ else {
+        return t;
+    };
+    match move_.pp_ups(content.max_pp) {
+        Ok(pp_ups) => t.push_str(&format!("{pp_ups} PP Up(s) used, base PP {}\n", move_.pp)),
+        Err(err) => t.push_str(&format!("Check failed: {err}\n")),
+    }
+    if !move_.type_.eq_ignore_ascii_case(&content.type_) {
+        t.push_str(&format!(
+            "Check failed: type {} does not match {} of {}\n",
+            content.type_, move_.type_, move_.name
+        ));
+    }
+    t
+}
+
+fn scan_rby_fight_menu(
+    img_gameboy: &GrayImage,
+    rby_fight_menu: &RbyFightMenu,
+    chars: &CharTable,
+    rby_moves: &Moves,
+) -> Result<String, String> {
+    let content = rby_fight_menu
+        .read_fields(img_gameboy, chars)
+        .map_err(|err| format!("could not read RBY FIGHT menu: {err}"))?;
+
+    let move_names = Dictionary::new(rby_moves.names(), chars);
+    let mut corrections = String::new();
+    let move_names = content.moves.clone().map(|name| match name.as_str() {
+        "-" => name,
+        _ => correct_name(&name, &move_names, &mut corrections),
+    });
+
+    let mut t = String::new();
+    let selected = rby_moves.get(&move_names[content.cursor]);
+    t.push_str(&fmt_fight_menu_pp(&content, selected));
+
+    t.push_str(&format!("\n{}\n", &fmt::fmt_move_header()));
+    for move_name in &move_names {
+        match move_name.as_str() {
+            "-" => t.push_str("-\n"),
+            _ => t.push_str(&format!("{}\n", fmt::fmt_move(rby_moves.get(move_name)))),
+        }
+    }
+    t.push_str(&corrections);
+
+    Ok(t)
+}
+
+fn scan_gsc_fight_menu(
+    img_gameboy: &GrayImage,
+    gsc_fight_menu: &GscFightMenu,
+    chars: &CharTable,
+    gsc_moves: &GscMoves,
+) -> Result<String, String> {
+    let content = gsc_fight_menu
+        .read_fields(img_gameboy, chars)
+        .map_err(|err| format!("could not read GSC FIGHT menu: {err}"))?;
+
+    let move_names = Dictionary::new(gsc_moves.names(), chars);
+    let mut corrections = String::new();
+    let move_names = content.moves.clone().map(|name| match name.as_str() {
+        "-" => name,
+        _ => correct_name(&name, &move_names, &mut corrections),
+    });
+
+    let mut t = String::new();
+    let selected = gsc_moves.get(&move_names[content.cursor]);
+    t.push_str(&fmt_fight_menu_pp(&content, selected));
+
+    t.push_str(&format!("\n{}\n", &fmt::fmt_move_header()));
+    for move_name in &move_names {
+        match move_name.as_str() {
+            "-" => t.push_str("-\n"),
+            _ => t.push_str(&format!("{}\n", fmt::fmt_move(gsc_moves.get(move_name)))),
+        }
+    }
+    t.push_str(&corrections);
+
+    Ok(t)
+}
+
+/// Returns the DV range of a stat other than the HP, with any stat exp.
+///
+/// The level-up stats do not show the stat exp., so every DV giving the
+/// stat with some stat exp. is possible.
+fn level_up_dv_range(level: i32, base: i32, stat: i32) -> Result<DvRange, String> {
+    let lowest = StatVariation::init(&level, &base, &0, &false);
+    let highest = StatVariation::init(&level, &base, &MAX_STAT_EXP, &false);
+    DvRange::init_within(&stat, &lowest, &highest)
+}
+
+/// Returns the HP DV, if the other DVs are known exactly.
+///
+/// The HP DV is made of the lowest bits of the other DVs.
+fn fmt_hp_dv(attack: &DvRange, defense: &DvRange, speed: &DvRange, special: &DvRange) -> String {
+    let ranges = [attack, defense, speed, special];
+    if ranges.iter().any(|range| range.min != range.max) {
+        return String::new();
+    }
+    let dv = hp_dv(attack.min, defense.min, speed.min, special.min);
+    format!("\nHP DV: {dv}\n")
+}
+
+fn scan_rby_level_up(
+    img_gameboy: &GrayImage,
+    rby_level_up: &RbyLevelUp,
+    chars: &CharTable,
+    rby_pokedex: &RbyPokedex,
+    session: &ScanSession,
+) -> Result<String, String> {
+    let content = rby_level_up
+        .read_fields(img_gameboy, chars)
+        .map_err(|err| format!("could not read RBY level-up stats: {err}"))?;
+
+    // The species of a nicknamed Pokémon is known from its summary
+    let pokemon = rby_pokedex
+        .get_ingame(&content.name)
+        .or_else(|| {
+            let species = session.species(&content.name)?;
+            rby_pokedex.get_pokemon(species)
+        })
+        .ok_or(format!(
+            "could not find the species of '{}', scan its summary first",
+            content.name
+        ))?;
+    let level = content.level;
+
+    let range_attack = level_up_dv_range(level, pokemon.attack, content.attack)
+        .map_err(|err| format!("could not determine Attack DV range: {err}"))?;
+    let range_defense = level_up_dv_range(level, pokemon.defense, content.defense)
+        .map_err(|err| format!("could not determine Defense DV range: {err}"))?;
+    let range_speed = level_up_dv_range(level, pokemon.speed, content.speed)
+        .map_err(|err| format!("could not determine Speed DV range: {err}"))?;
+    let range_special = level_up_dv_range(level, pokemon.special, content.special)
+        .map_err(|err| format!("could not determine Special DV range: {err}"))?;
+
+    let mut t = String::new();
+    t.push_str(&format!(
+        "No.{} {} :L{level}\n\n",
+        pokemon.ndex, pokemon.name
+    ));
+    t.push_str(&fmt::fmt_stat_header());
+    t.push_str(&fmt::fmt_stat_row(
+        "ATT",
+        &pokemon.attack,
+        &content.attack,
+        &range_attack,
+    ));
+    t.push_str(&fmt::fmt_stat_row(
+        "DEF",
+        &pokemon.defense,
+        &content.defense,
+        &range_defense,
+    ));
+    t.push_str(&fmt::fmt_stat_row(
+        "SPD",
+        &pokemon.speed,
+        &content.speed,
+        &range_speed,
+    ));
+    t.push_str(&fmt::fmt_stat_row(
+        "SPC",
+        &pokemon.special,
+        &content.special,
+        &range_special,
+    ));
+    t.push_str(&fmt_hp_dv(
+        &range_attack,
+        &range_defense,
+        &range_speed,
+        &range_special,
+    ));
+
+    Ok(t)
+}
+
+fn scan_gsc_level_up(
+    img_gameboy: &GrayImage,
+    gsc_level_up: &GscLevelUp,
+    chars: &CharTable,
+    gsc_pokedex: &GscPokedex,
+    session: &ScanSession,
+) -> Result<String, String> {
+    let content = gsc_level_up
+        .read_fields(img_gameboy, chars)
+        .map_err(|err| format!("could not read GSC level-up stats: {err}"))?;
+
+    // The species of a nicknamed Pokémon is known from its summary
+    let pokemon = gsc_pokedex
+        .get_ingame(&content.name)
+        .or_else(|| {
+            let species = session.species(&content.name)?;
+            gsc_pokedex.get_pokemon(species)
+        })
+        .ok_or(format!(
+            "could not find the species of '{}', scan its summary first",
+            content.name
+        ))?;
+    let level = content.level;
+
+    let range_attack = level_up_dv_range(level, pokemon.attack, content.attack)
+        .map_err(|err| format!("could not determine Attack DV range: {err}"))?;
+    let range_defense = level_up_dv_range(level, pokemon.defense, content.defense)
+        .map_err(|err| format!("could not determine Defense DV range: {err}"))?;
+    let range_spc_attack = level_up_dv_range(level, pokemon.special_attack, content.spc_attack)
+        .map_err(|err| format!("could not determine Spc. Attack DV range: {err}"))?;
+    let range_spc_defense = level_up_dv_range(level, pokemon.special_defense, content.spc_defense)
+        .map_err(|err| format!("could not determine Spc. Defense DV range: {err}"))?;
+    let range_speed = level_up_dv_range(level, pokemon.speed, content.speed)
+        .map_err(|err| format!("could not determine Speed DV range: {err}"))?;
+
+    // Both special stats share the Special DV
+    let range_special = DvRange {
+        min: range_spc_attack.min.max(range_spc_defense.min),
+        max: range_spc_attack.max.min(range_spc_defense.max),
+    };
+
+    let mut t = String::new();
+    t.push_str(&format!(
+        "No.{} {} :L{level}\n\n",
+        pokemon.ndex, pokemon.name
+    ));
+    t.push_str(&fmt::fmt_stat_header());
+    t.push_str(&fmt::fmt_stat_row(
+        "ATT",
+        &pokemon.attack,
+        &content.attack,
+        &range_attack,
+    ));
+    t.push_str(&fmt::fmt_stat_row(
+        "DEF",
+        &pokemon.defense,
+        &content.defense,
+        &range_defense,
+    ));
+    t.push_str(&fmt::fmt_stat_row(
+        "SPA",
+        &pokemon.special_attack,
+        &content.spc_attack,
+        &range_spc_attack,
+    ));
+    t.push_str(&fmt::fmt_stat_row(
+        "SPD",
+        &pokemon.special_defense,
+        &content.spc_defense,
+        &range_spc_defense,
+    ));
+    t.push_str(&fmt::fmt_stat_row(
+        "SPE",
+        &pokemon.speed,
+        &content.speed,
+        &range_speed,
+    ));
+    if range_special.min > range_special.max {
+        t.push_str("\nCheck failed: the special stats do not share a Special DV\n");
+        return Ok(t);
+    }
+    t.push_str(&fmt_hp_dv(
+        &range_attack,
+        &range_defense,
+        &range_speed,
+        &range_special,
+    ));
+
+    Ok(t)
+}
+
+/// Returns the lines of a Pokédex entry, with the size if caught.
+fn fmt_pokedex_entry(content: &PokedexEntryContent) -> String {
+    let mut t = format!("{} Pokémon\n", content.category);
+    match (content.height, content.weight) {
+        (Some((feet, inches)), Some(weight)) => {
+            t.push_str(&format!("HT {feet}'{inches:02}\"  WT {weight:.1} lb\n"));
+        }
+        _ => t.push_str("Not caught yet\n"),
+    }
+    if !content.description.is_empty() {
+        t.push_str(&format!("{}\n", content.description));
+    }
+    t
+}
+
+/// Returns the evo chains with the Pokémon and the Pokémon within them.
+fn find_evo_chains<'a>(evo_chains: &'a [String], name: &str) -> (Vec<&'a String>, Vec<&'a str>) {
+    let evo_chains: Vec<&String> = evo_chains
+        .iter()
+        .filter(|x| x.split("->").step_by(2).any(|x| x == name))
+        .collect();
+
+    let mut names: Vec<&str> = Vec::new();
+    for chain in &evo_chains {
+        for name in chain.split("->").step_by(2) {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+    (evo_chains, names)
+}
+
+fn scan_rby_pokedex_entry(
+    img_gameboy: &GrayImage,
+    rby_pokedex_entry: &RbyPokedexEntry,
+    chars: &CharTable,
+    rby_pokedex: &RbyPokedex,
+    rby_evo_chains: &[String],
+    rby_learnsets: &RbyLearnsets,
+    rby_moves: &Moves,
+) -> Result<String, String> {
+    let content = rby_pokedex_entry
+        .read_fields(img_gameboy, chars)
+        .map_err(|err| format!("could not read RBY Pokédex entry: {err}"))?;
+    let pokemon = usize::try_from(content.ndex)
+        .ok()
+        .filter(|ndex| *ndex > 0)
+        .and_then(|ndex| rby_pokedex.get_ndex(ndex))
+        .ok_or(format!("could not find Pokemon at ndex '{}'", content.ndex))?;
+
+    let mut t = format!("No.{} {}\n", pokemon.ndex, pokemon.name);
+    if ingame_name(&pokemon.name) != content.name {
+        t.push_str(&format!(
+            "Check failed: name '{}' does not match No.{}\n",
+            content.name, pokemon.ndex
+        ));
+    }
+    t.push_str(&fmt_pokedex_entry(&content));
+    t.push_str(&format!(
+        "Type: {}\n\n",
+        fmt_types(&pokemon.type1, &pokemon.type2)
+    ));
+    t.push_str(&fmt::fmt_base_stats(&[
+        ("HP", pokemon.hp),
+        ("ATT", pokemon.attack),
+        ("DEF", pokemon.defense),
+        ("SPD", pokemon.speed),
+        ("SPC", pokemon.special),
+    ]));
+
+    let (evo_chains, names) = find_evo_chains(rby_evo_chains, &pokemon.name);
+    t.push_str("\nEvo chain(s)\n");
+    for chain in evo_chains {
+        t.push_str(&format!("{}\n", chain.replace("->", "   ->   ")));
+    }
+    for name in names {
+        let learnset = rby_learnsets
+            .get_pokemon(name)
+            .ok_or(format!("no learnset found for Pokemon '{name}'"))?;
+        t.push_str(&format!("\n{}\n", fmt::fmt_learnset(learnset, rby_moves)?));
+    }
+
+    Ok(t)
+}
+
+fn scan_gsc_pokedex_entry(
+    img_gameboy: &GrayImage,
+    gsc_pokedex_entry: &GscPokedexEntry,
+    chars: &CharTable,
+    gsc_pokedex: &GscPokedex,
+    gsc_evo_chains: &[String],
+    gsc_learnsets: &GscLearnsets,
+    gsc_moves: &GscMoves,
+) -> Result<String, String> {
+    let content = gsc_pokedex_entry
+        .read_fields(img_gameboy, chars)
+        .map_err(|err| format!("could not read GSC Pokédex entry: {err}"))?;
+    let pokemon = usize::try_from(content.ndex)
+        .ok()
+        .filter(|ndex| *ndex > 0)
+        .and_then(|ndex| gsc_pokedex.get_ndex(ndex))
+        .ok_or(format!("could not find Pokemon at ndex '{}'", content.ndex))?;
+
+    let mut t = format!("No.{} {}\n", pokemon.ndex, pokemon.name);
+    if ingame_name(&pokemon.name) != content.name {
+        t.push_str(&format!(
+            "Check failed: name '{}' does not match No.{}\n",
+            content.name, pokemon.ndex
+        ));
+    }
+    t.push_str(&fmt_pokedex_entry(&content));
+    t.push_str(&format!(
+        "Type: {}\n\n",
+        fmt_types(&pokemon.type1, &pokemon.type2)
+    ));
+    t.push_str(&fmt::fmt_base_stats(&[
+        ("HP", pokemon.hp),
+        ("ATT", pokemon.attack),
+        ("DEF", pokemon.defense),
+        ("SPA", pokemon.special_attack),
+        ("SPD", pokemon.special_defense),
+        ("SPE", pokemon.speed),
+    ]));
+
+    let (evo_chains, names) = find_evo_chains(gsc_evo_chains, &pokemon.name);
+    t.push_str("\nEvo chain(s)\n");
+    for chain in evo_chains {
+        t.push_str(&format!("{}\n", chain.replace("->", "   ->   ")));
+    }
+    for name in names {
+        let learnset = gsc_learnsets
+            .get_pokemon(name)
+            .ok_or(format!("no learnset found for Pokemon '{name}'"))?;
+        t.push_str(&format!(
+            "\n{}\n",
+            fmt::fmt_gsc_learnset(learnset, gsc_moves)?
+        ));
+    }
+
+    Ok(t)
+}
+
+/// Returns the lines of the trainer card.
+fn fmt_trainer_card(content: &TrainerCardContent) -> String {
+    let mut t = format!("Trainer {}\n", content.name);
+    if let Some(id) = content.id {
+        t.push_str(&format!("ID No. {id:05}\n"));
+    }
+    t.push_str(&format!("Money: {}\n", content.money));
+    let (hours, minutes) = content.play_time;
+    t.push_str(&format!("Play time: {hours}:{minutes:02}\n"));
+    t
+}
+
+/// Returns the line telling that a Pokémon was traded, if the trainer card
+/// was scanned and the OT could be read.
+fn fmt_traded(session: &ScanSession, ot: Option<&str>, id: Option<i32>) -> String {
+    match (session.trainer(), ot) {
+        (Some(trainer), Some(ot)) if trainer.is_traded(ot, id) => {
+            "\nTraded, gains boosted EXP.\n".to_string()
+        }
+        _ => String::new(),
+    }
+}
+
+/// Returns the stats in battle, boosted by the badges obtained.
+///
+/// The stats are given by their label, name and value, e.g. ("ATT",
+/// "Attack", 55). Empty if none of the badges boosts a stat.
+fn fmt_badge_boosts(stats: &[(&str, &str, i32)], badges: &[&str]) -> String {
+    let mut t = String::new();
+    for (label, name, stat) in stats {
+        let boosting = RBY_BADGE_BOOSTS
+            .iter()
+            .find(|(badge, boosted)| boosted == name && badges.contains(badge));
+        if let Some((badge, _)) = boosting {
+            t.push_str(&format!(
+                "{label:>3}  {stat:>3} -> {:>3}  {badge}\n",
+                badge_boost(*stat)
+            ));
+        }
+    }
+    match t.is_empty() {
+        true => t,
+        false => format!("\nIn battle, boosted by the badges\n{t}"),
+    }
+}
+
+fn scan_rby_trainer_card(
+    img_gameboy: &GrayImage,
+    rby_trainer_card: &RbyTrainerCard,
+    chars: &CharTable,
+    session: &mut ScanSession,
+) -> Result<String, String> {
+    let content = rby_trainer_card
+        .read_fields(img_gameboy, chars)
+        .map_err(|err| format!("could not read RBY trainer card: {err}"))?;
+
+    let t = fmt_trainer_card(&content);
+    session.record_trainer(content);
+    Ok(t)
+}
+
+fn scan_gsc_trainer_card(
+    img_gameboy: &GrayImage,
+    gsc_trainer_card: &GscTrainerCard,
+    chars: &CharTable,
+    session: &mut ScanSession,
+) -> Result<String, String> {
+    let content = gsc_trainer_card
+        .read_fields(img_gameboy, chars)
+        .map_err(|err| format!("could not read GSC trainer card: {err}"))?;
+
+    let t = fmt_trainer_card(&content);
+    session.record_trainer(content);
+    session.record_gsc_first_page();
+    Ok(t)
+}
+
+fn scan_gsc_badge_page(
+    img_gameboy: &GrayImage,
+    gsc_badge_page: &GscBadgePage,
+    session: &mut ScanSession,
+) -> Result<String, String> {
+    let leaders = gsc_badge_page
+        .read_leaders(img_gameboy)
+        .map_err(|err| format!("could not read GSC badge page: {err}"))?;
+    let page = session.turn_gsc_card_page(leaders)?;
+    let badges = gsc_badge_page
+        .read_badges(img_gameboy, page.badges())
+        .map_err(|err| format!("could not read GSC badge page: {err}"))?;
+    session.record_badges(page.badges(), &badges);
+
+    let region = match page {
+        GscCardPage::Kanto => "Kanto",
+        _ => "Johto",
+    };
+    let names = match badges.is_empty() {
+        true => "-".to_string(),
+        false => badges.join(", "),
+    };
+    Ok(format!("{region} badges: {names} ({}/8)\n", badges.len()))
+}
+
+/// Returns the line of a Pokémon of the party.
+///
+/// The species is shown if known, e.g. "No.25 Pikachu". The status is left
+/// out while the Pokémon is OK.
+fn fmt_party_slot(idx: usize, slot: &PartySlot, species: Option<String>) -> String {
+    let mut t = format!("{} {}", idx + 1, slot.nickname);
+    if let Some(species) = species {
+        t.push_str(&format!(" ({species})"));
+    }
+    t.push_str(&format!(
+        " :L{}  HP {}/{}",
+        slot.level, slot.hp, slot.max_hp
+    ));
+    if slot.status != "OK" {
+        t.push_str(&format!("  {}", slot.status));
+    }
+    t.push('\n');
+    t
+}
+
+fn scan_rby_party(
+    img_gameboy: &GrayImage,
+    rby_party: &RbyParty,
+    chars: &CharTable,
+    rby_pokedex: &RbyPokedex,
+    session: &mut ScanSession,
+) -> Result<String, String> {
+    let party = rby_party
+        .read_fields(img_gameboy, chars)
+        .map_err(|err| format!("could not read RBY party: {err}"))?;
+
+    let mut t = String::new();
+    let mut species = Vec::new();
+    for (idx, slot) in party.iter().enumerate() {
+        // Nicknamed Pokémon are known from their summaries
+        let pokemon = rby_pokedex.get_ingame(&slot.nickname).or_else(|| {
+            let name = session.species(&slot.nickname)?;
+            rby_pokedex.get_pokemon(name)
+        });
+        if let Some(pokemon) = pokemon {
+            species.push(pokemon.name.clone());
+        }
+        let pokemon = pokemon.map(|p| format!("No.{} {}", p.ndex, p.name));
+        t.push_str(&fmt_party_slot(idx, slot, pokemon));
+    }
+    session.record_party(species);
+    Ok(t)
+}
+
+fn scan_gsc_party(
+    img_gameboy: &GrayImage,
+    gsc_party: &GscParty,
+    chars: &CharTable,
+    gsc_pokedex: &GscPokedex,
+    session: &mut ScanSession,
+) -> Result<String, String> {
+    let party = gsc_party
+        .read_fields(img_gameboy, chars)
+        .map_err(|err| format!("could not read GSC party: {err}"))?;
+
+    let mut t = String::new();
+    let mut species = Vec::new();
+    for (idx, slot) in party.iter().enumerate() {
+        // Nicknamed Pokémon are known from their summaries
+        let pokemon = gsc_pokedex.get_ingame(&slot.nickname).or_else(|| {
+            let name = session.species(&slot.nickname)?;
+            gsc_pokedex.get_pokemon(name)
+        });
+        if let Some(pokemon) = pokemon {
+            species.push(pokemon.name.clone());
+        }
+        let pokemon = pokemon.map(|p| format!("No.{} {}", p.ndex, p.name));
+        t.push_str(&fmt_party_slot(idx, slot, pokemon));
+    }
+    session.record_party(species);
+    Ok(t)
+}
+
+/// Returns the line of an item of the bag, with the description if known.
+fn fmt_bag_item(item: &BagItem, description: Option<&str>) -> String {
+    let quantity = item.quantity.map_or(String::new(), |x| format!(" ×{x}"));
+    match description {
+        Some(description) => format!("{}{quantity}  {description}\n", item.name),
+        None => format!("{}{quantity}\n", item.name),
+    }
+}
+
+/// Returns the party members learning the move of the TM or HM.
+///
+/// The party members are given by their species. The learnsets without the
+/// TMs and HMs can not tell, these party members are listed as unknown.
+fn fmt_tm_hm_learners<'a>(
+    item: &str,
+    party: &[&str],
+    learnset: impl Fn(&str) -> Option<&'a Learnset>,
+) -> String {
+    let mut learners = Vec::new();
+    let mut unknown = Vec::new();
+    for species in party {
+        match learnset(species).and_then(|x| x.learns_tm_hm(item)) {
+            Some(true) => learners.push(*species),
+            Some(false) => (),
+            None => unknown.push(*species),
+        }
+    }
+
+    let mut t = String::new();
+    if unknown.len() < party.len() {
+        let learners = match learners.is_empty() {
+            true => "-".to_string(),
+            false => learners.join(", "),
+        };
+        t.push_str(&format!("  Learned by: {learners}\n"));
+    }
+    if !unknown.is_empty() {
+        t.push_str(&format!(
+            "  Unknown for: {} (no TM/HM learnset data)\n",
+            unknown.join(", ")
+        ));
+    }
+    t
+}
+
+/// Returns the lines of the items of the bag.
+///
+/// The TMs and HMs are shown with their move and the party members
+/// learning it.
+fn fmt_bag<'a>(
+    content: &BagContent,
+    description: impl Fn(&str) -> Option<&'a str>,
+    tm_hms: &TmHms,
+    party: &[&str],
+    learnset: impl Fn(&str) -> Option<&'a Learnset>,
+) -> String {
+    let mut t = String::new();
+    for item in &content.items {
+        let Some(move_name) = tm_hms.get(&item.name) else {
+            t.push_str(&fmt_bag_item(item, description(&item.name)));
+            continue;
+        };
+        t.push_str(&fmt_bag_item(item, Some(move_name)));
+        if let Some(tm_hm) = item.name.get(..4) {
+            t.push_str(&fmt_tm_hm_learners(tm_hm, party, &learnset));
+        }
+    }
+    if content.is_end {
+        t.push_str("End of list\n");
+    }
+    t
+}
+
+fn scan_rby_bag(
+    img_gameboy: &GrayImage,
+    rby_bag: &RbyBag,
+    chars: &CharTable,
+    party: &[&str],
+) -> Result<String, String> {
+    let content = rby_bag
+        .read_fields(img_gameboy, chars)
+        .map_err(|err| format!("could not read RBY bag: {err}"))?;
+
+    let rby_items = RbyItems::new();
+    let rby_learnsets = RbyLearnsets::new();
+    Ok(fmt_bag(
+        &content,
+        |name| rby_items.get(name).map(|x| x.description.as_str()),
+        &TmHms::rby(),
+        party,
+        |species| rby_learnsets.get_pokemon(species),
+    ))
+}
+
+fn scan_gsc_bag(
+    img_gameboy: &GrayImage,
+    gsc_bag: &GscBag,
+    chars: &CharTable,
+    party: &[&str],
+) -> Result<String, String> {
+    let content = gsc_bag
+        .read_fields(img_gameboy, chars)
+        .map_err(|err| format!("could not read GSC bag: {err}"))?;
+
+    let gsc_items = GscItems::new();
+    let gsc_learnsets = GscLearnsets::new();
+    Ok(fmt_bag(
+        &content,
+        |name| gsc_items.get(name).map(|x| x.description.as_str()),
+        &TmHms::gsc(),
+        party,
+        |species| gsc_learnsets.get_pokemon(species),
+    ))
+}
+
+/// Returns the line of a Pokémon of the box, e.g. "2 SPARKY (PIKACHU) :L35".
+///
+/// The species and level are shown if known.
+fn fmt_box_entry(idx: usize, entry: &BoxEntry) -> String {
+    let mut t = format!("{} {}", idx + 1, entry.name);
+    if let Some(species) = &entry.species {
+        t.push_str(&format!(" ({species})"));
+    }
+    if let Some(level) = entry.level {
+        t.push_str(&format!(" :L{level}"));
+    }
+    t.push('\n');
+    t
+}
+
+/// Returns the box of Bill's PC, as far as it was scanned.
+fn fmt_box_inventory(inventory: &BoxInventory) -> String {
+    let mut t = format!("Box, {} Pokémon scanned", inventory.entries().len());
+    if !inventory.has_end() {
+        t.push_str(", scroll down for more");
+    }
+    t.push('\n');
+    for (idx, entry) in inventory.entries().iter().enumerate() {
+        t.push_str(&fmt_box_entry(idx, entry));
+    }
+    t
+}
+
+/// The options of scanning an image.
+pub struct ScanOptions {
+    /// The aspect models the Game Boy screen is searched with.
+    pub aspect_models: Vec<AspectModel>,
+    /// The user-provided position of the screen, skips locating the screen.
+    pub screen_pos: Option<Position>,
+    /// The characters of a custom font, e.g. of a ROM hack.
+    ///
+    /// Extends the built-in characters, replacing the glyphs in common.
+    pub font: Option<CharTable>,
+}
+
+impl ScanOptions {
+    /// Creates the options that scan the calibrated screen region.
+    pub fn from_calibration(calibration: &Calibration) -> ScanOptions {
+        ScanOptions {
+            aspect_models: vec![calibration.model],
+            screen_pos: Some(calibration.pos),
+            font: None,
+        }
+    }
+}
+
+impl Default for ScanOptions {
+    fn default() -> Self {
+        ScanOptions {
+            aspect_models: AspectModel::defaults(),
+            screen_pos: None,
+            font: None,
+        }
+    }
+}
+
+/// Locates the game screen and returns it as a native binary image.
+fn read_native_screen(
+    img_screen: &DynamicImage,
+    options: &ScanOptions,
+) -> Result<GrayImage, String> {
     let (w, h) = (img_screen.width(), img_screen.height());
     let (w_min, h_min) = (160, 144);
     if w < w_min || h < h_min {
@@ -631,8 +1578,68 @@ pub fn scan_img(img_screen: DynamicImage) -> Result<String, String> {
         ));
     }
 
+    let (gameboy_pos, aspect_model) = match options.screen_pos {
+        Some(pos) => {
+            if pos.end().is_none_or(|(x1, y1)| x1 > w || y1 > h) {
+                return Err(format!(
+                    "screen region {pos:?} does not fit into the image of {w}x{h} pixels"
+                ));
+            }
+            let model = AspectModel::closest(&options.aspect_models, &pos)
+                .ok_or("no aspect model was provided")?;
+            (pos, model)
+        }
+        None => pkmn::gameboy::locate_screen_with(img_screen, &options.aspect_models)
+            .ok_or("could not locate Game Boy screen")?,
+    };
+
+    let img_gameboy = aspect_model.to_native(img_screen, &gameboy_pos);
+
+    Ok(pkmn::palette::normalize_palette(&img_gameboy))
+}
+
+/// Returns the characters to read the screen with, including the custom font.
+fn char_table(options: &ScanOptions) -> CharTable {
+    let mut chars = pkmn::ocr::CharTable::new();
+    if let Some(font) = &options.font {
+        chars.extend(font);
+    }
+    chars
+}
+
+/// Locates and reads the game screen and returns the details of the Pokemon.
+///
+/// A one-stop function to locate the game on the input image, to read its
+/// content and to return the relevant info in a human readable form.
+/// Works with the summary screens, the level-up stats, the Pokédex entries,
+/// the trainer cards and the party menu of RBY and GSC.
+/// On the battle screens, reports the opponent's types, weaknesses, stats
+/// and moves, and on the FIGHT menu, the PP of the moves.
+pub fn scan_img(img_screen: DynamicImage) -> Result<String, String> {
+    scan_img_with(img_screen, &ScanOptions::default())
+}
+
+/// Locates and reads the game screen with the given options.
+///
+/// See [`scan_img`] for details.
+pub fn scan_img_with(img_screen: DynamicImage, options: &ScanOptions) -> Result<String, String> {
+    scan_img_with_session(img_screen, options, &mut ScanSession::new())
+}
+
+/// Locates and reads the game screen, remembering what it shows.
+///
+/// The species of nicknamed Pokémon are taken from their summaries scanned
+/// earlier in the session, the player and the badges from the trainer card.
+/// See [`scan_img`] for details.
+pub fn scan_img_with_session(
+    img_screen: DynamicImage,
+    options: &ScanOptions,
+    session: &mut ScanSession,
+) -> Result<String, String> {
+    let img_gameboy = read_native_screen(&img_screen, options)?;
+
     // Init data
-    let chars = pkmn::ocr::CharTable::new();
+    let chars = char_table(options);
 
     let rby_pokedex = pkmn::pokemon::RbyPokedex::new();
     let rby_learnsets = pkmn::learnset::RbyLearnsets::new();
@@ -645,6 +1652,9 @@ pub fn scan_img(img_screen: DynamicImage) -> Result<String, String> {
     let gsc_moves = pkmn::moves::GscMoves::new();
     let gsc_items = pkmn::items::GscItems::new();
 
+    let rby_types = pkmn::types::TypeChart::rby();
+    let gsc_types = pkmn::types::TypeChart::gsc();
+
     let rby_summary_1 = pkmn::gameboy::RbySummary1::new();
     let rby_summary_2 = pkmn::gameboy::RbySummary2::new();
 
@@ -652,42 +1662,33 @@ pub fn scan_img(img_screen: DynamicImage) -> Result<String, String> {
     let gsc_summary_2 = pkmn::gameboy::GscSummary2::new();
     let gsc_summary_3 = pkmn::gameboy::GscSummary3::new();
 
+    let rby_level_up = pkmn::gameboy::RbyLevelUp::new();
+    let gsc_level_up = pkmn::gameboy::GscLevelUp::new();
+
+    let rby_pokedex_entry = pkmn::gameboy::RbyPokedexEntry::new();
+    let gsc_pokedex_entry = pkmn::gameboy::GscPokedexEntry::new();
+
+    let rby_trainer_card = pkmn::gameboy::RbyTrainerCard::new();
+    let gsc_trainer_card = pkmn::gameboy::GscTrainerCard::new();
+    let gsc_badge_page = pkmn::gameboy::GscBadgePage::new();
+
+    let rby_battle = pkmn::gameboy::RbyBattle::new();
+    let gsc_battle = pkmn::gameboy::GscBattle::new();
+    let rby_fight_menu = pkmn::gameboy::RbyFightMenu::new();
+    let gsc_fight_menu = pkmn::gameboy::GscFightMenu::new();
+    let rby_party = pkmn::gameboy::RbyParty::new();
+    let gsc_party = pkmn::gameboy::GscParty::new();
+
     // Do actual scanning
-    let gameboy_pos =
-        pkmn::gameboy::locate_screen(&img_screen).ok_or("could not locate Game Boy screen")?;
-
-    let img_gameboy = img_screen
-        .clone()
-        .crop(
-            gameboy_pos.x,
-            gameboy_pos.y,
-            gameboy_pos.width,
-            gameboy_pos.height,
-        )
-        .resize_exact(
-            rby_summary_1.width as u32,
-            rby_summary_1.height as u32,
-            image::imageops::FilterType::Nearest,
-        );
-
-    let mut img_gameboy = img_gameboy.to_luma8();
-    let threshold_val = 140; // Anything in [30, 170]
-    threshold_mut(&mut img_gameboy, threshold_val);
-    invert(&mut img_gameboy);
-
-    let is_rby_summary_1 = rby_summary_1.verify_layout(&img_gameboy, &chars);
-    let is_rby_summary_2 = rby_summary_2.verify_layout(&img_gameboy, &chars);
-    let is_gsc_summary_1 = gsc_summary_1.verify_layout(&img_gameboy, &chars);
-    let is_gsc_summary_2 = gsc_summary_2.verify_layout(&img_gameboy, &chars);
-    let is_gsc_summary_3 = gsc_summary_3.verify_layout(&img_gameboy, &chars);
-
-    if is_rby_summary_1 {
-        let result = scan_rby_summary_1(&img_gameboy, &rby_summary_1, &chars, &rby_pokedex);
-        return result;
-    }
-
-    if is_rby_summary_2 {
-        let result = scan_rby_summary_2(
+    let tilemap = pkmn::tilemap::TileMap::read(&img_gameboy, &chars)?;
+    let classification = pkmn::screen::ScreenClassifier::new().classify(&tilemap);
+    let classification = classification.ok_or("could not recognize screen layout")?;
+
+    match classification.kind {
+        ScreenKind::RbySummary1 => {
+            scan_rby_summary_1(&img_gameboy, &rby_summary_1, &chars, &rby_pokedex, session)
+        }
+        ScreenKind::RbySummary2 => scan_rby_summary_2(
             &img_gameboy,
             &rby_summary_2,
             &chars,
@@ -695,17 +1696,11 @@ pub fn scan_img(img_screen: DynamicImage) -> Result<String, String> {
             &rby_evo_chains,
             &rby_learnsets,
             &rby_moves,
-        );
-        return result;
-    }
-
-    if is_gsc_summary_1 {
-        let result = scan_gsc_summary_1(&img_gameboy, &gsc_summary_1, &chars, &gsc_pokedex);
-        return result;
-    }
-
-    if is_gsc_summary_2 {
-        let result = scan_gsc_summary_2(
+        ),
+        ScreenKind::GscSummary1 => {
+            scan_gsc_summary_1(&img_gameboy, &gsc_summary_1, &chars, &gsc_pokedex, session)
+        }
+        ScreenKind::GscSummary2 => scan_gsc_summary_2(
             &img_gameboy,
             &gsc_summary_2,
             &chars,
@@ -714,14 +1709,153 @@ pub fn scan_img(img_screen: DynamicImage) -> Result<String, String> {
             &gsc_moves,
             &gsc_evo_chains,
             &gsc_learnsets,
-        );
-        return result;
+        ),
+        ScreenKind::GscSummary3 => {
+            scan_gsc_summary_3(&img_gameboy, &gsc_summary_3, &chars, &gsc_pokedex, session)
+        }
+        ScreenKind::RbyLevelUp => {
+            scan_rby_level_up(&img_gameboy, &rby_level_up, &chars, &rby_pokedex, session)
+        }
+        ScreenKind::GscLevelUp => {
+            scan_gsc_level_up(&img_gameboy, &gsc_level_up, &chars, &gsc_pokedex, session)
+        }
+        ScreenKind::RbyPokedexEntry => scan_rby_pokedex_entry(
+            &img_gameboy,
+            &rby_pokedex_entry,
+            &chars,
+            &rby_pokedex,
+            &rby_evo_chains,
+            &rby_learnsets,
+            &rby_moves,
+        ),
+        ScreenKind::GscPokedexEntry => scan_gsc_pokedex_entry(
+            &img_gameboy,
+            &gsc_pokedex_entry,
+            &chars,
+            &gsc_pokedex,
+            &gsc_evo_chains,
+            &gsc_learnsets,
+            &gsc_moves,
+        ),
+        ScreenKind::RbyTrainerCard => {
+            scan_rby_trainer_card(&img_gameboy, &rby_trainer_card, &chars, session)
+        }
+        ScreenKind::GscTrainerCard => {
+            scan_gsc_trainer_card(&img_gameboy, &gsc_trainer_card, &chars, session)
+        }
+        ScreenKind::GscBadgePage => scan_gsc_badge_page(&img_gameboy, &gsc_badge_page, session),
+        ScreenKind::RbyBattle => scan_rby_battle(
+            &img_gameboy,
+            &rby_battle,
+            &chars,
+            &rby_pokedex,
+            &rby_learnsets,
+            &rby_types,
+        ),
+        ScreenKind::GscBattle => scan_gsc_battle(
+            &img_gameboy,
+            &gsc_battle,
+            &chars,
+            &gsc_pokedex,
+            &gsc_learnsets,
+            &gsc_types,
+        ),
+        ScreenKind::RbyFightMenu => {
+            scan_rby_fight_menu(&img_gameboy, &rby_fight_menu, &chars, &rby_moves)
+        }
+        ScreenKind::GscFightMenu => {
+            scan_gsc_fight_menu(&img_gameboy, &gsc_fight_menu, &chars, &gsc_moves)
+        }
+        ScreenKind::RbyParty => {
+            scan_rby_party(&img_gameboy, &rby_party, &chars, &rby_pokedex, session)
+        }
+        ScreenKind::GscParty => {
+            scan_gsc_party(&img_gameboy, &gsc_party, &chars, &gsc_pokedex, session)
+        }
+    }
+}
+
+/// Locates and reads the bag and returns the details of the items.
+///
+/// The item lists show no static text, so the bag is not recognized by
+/// [`scan_img`]. The TMs and HMs are shown with their move and the party
+/// members learning it, given by their species, e.g. "Pikachu".
+pub fn scan_bag_with(
+    img_screen: DynamicImage,
+    options: &ScanOptions,
+    party: &[&str],
+) -> Result<String, String> {
+    let img_gameboy = read_native_screen(&img_screen, options)?;
+    let chars = char_table(options);
+
+    let rby_bag = pkmn::gameboy::RbyBag::new();
+    let gsc_bag = pkmn::gameboy::GscBag::new();
+
+    if rby_bag.verify_layout(&img_gameboy, &chars) {
+        return scan_rby_bag(&img_gameboy, &rby_bag, &chars, party);
     }
+    if gsc_bag.verify_layout(&img_gameboy, &chars) {
+        return scan_gsc_bag(&img_gameboy, &gsc_bag, &chars, party);
+    }
+    Err("could not recognize the bag".to_string())
+}
 
-    if is_gsc_summary_3 {
-        let result = scan_gsc_summary_3(&img_gameboy, &gsc_summary_3, &chars, &gsc_pokedex);
-        return result;
+/// Locates and reads the bag, with the party taken from the session.
+///
+/// The party is the one of the party menu scanned last, see
+/// [`scan_bag_with`]. Nicknamed members are known only if their summary was
+/// scanned before the party menu.
+pub fn scan_bag_with_session(
+    img_screen: DynamicImage,
+    options: &ScanOptions,
+    session: &ScanSession,
+) -> Result<String, String> {
+    let party: Vec<&str> = session.party().iter().map(|x| x.as_str()).collect();
+    let mut t = scan_bag_with(img_screen, options, &party)?;
+    if party.is_empty() {
+        t.push_str("\nScan the party menu to see which members learn the TMs and HMs.\n");
     }
+    Ok(t)
+}
 
-    return Err("could not recognize screen layout".to_string());
+/// Locates and reads the box list of Bill's PC and returns the box so far.
+///
+/// The box lists show no static text, so they are not recognized by
+/// [`scan_img`]. The scans are put together in the session, a list not
+/// continuing the scanned one starts a new box. The entries are numbered
+/// from the highest one scanned.
+pub fn scan_box_with_session(
+    img_screen: DynamicImage,
+    options: &ScanOptions,
+    session: &mut ScanSession,
+) -> Result<String, String> {
+    let img_gameboy = read_native_screen(&img_screen, options)?;
+    let chars = char_table(options);
+
+    let rby_box_list = pkmn::gameboy::RbyBoxList::new();
+    let gsc_box_list = pkmn::gameboy::GscBoxList::new();
+
+    let content = if rby_box_list.verify_layout(&img_gameboy, &chars) {
+        rby_box_list
+            .read_fields(&img_gameboy, &chars)
+            .map_err(|err| format!("could not read RBY box list: {err}"))?
+    } else if gsc_box_list.verify_layout(&img_gameboy, &chars) {
+        gsc_box_list
+            .read_fields(&img_gameboy, &chars)
+            .map_err(|err| format!("could not read GSC box list: {err}"))?
+    } else {
+        return Err("could not recognize the box list".to_string());
+    };
+
+    let mut t = String::new();
+    if session.box_inventory().alignments(&content).is_empty() {
+        *session.box_inventory_mut() = BoxInventory::new();
+        t.push_str("New box started\n");
+    }
+    session
+        .box_inventory_mut()
+        .add(&content)
+        .map_err(|err| format!("could not add the box list: {err}"))?;
+    t.push_str(&fmt_box_inventory(session.box_inventory()));
+    Ok(t)
 }
@@ -1,16 +1,23 @@
 mod utils;
 
 use core as pkmn;
+use pkmn::calibration::Calibration;
+use pkmn::gameboy::{parse_badges, RBY_BADGES};
+use pkmn::position::Position;
+use pkmn::session::ScanSession;
+use pkmn::utils::ScanOptions;
+use std::cell::RefCell;
 use wasm_bindgen::prelude::*;
 
 use image::{DynamicImage, ImageBuffer, Rgba};
 
-/// Locates the GameBoy, reads the contents of the summary screen 1
-/// and returns the stats of the found pokemon.
-#[wasm_bindgen]
-pub fn read_stats_from_screen(data: &[u8], width: u32, height: u32) -> Result<JsValue, JsValue> {
-    utils::set_panic_hook();
+thread_local! {
+    /// The player's data seen on the earlier screens, kept between the scans.
+    static SESSION: RefCell<ScanSession> = RefCell::new(ScanSession::new());
+}
 
+/// Creates an image from the pixelData of the canvas.
+fn image_from_pixel_data(data: &[u8], width: u32, height: u32) -> Result<DynamicImage, JsValue> {
     if data.len() != (width * height * 4) as usize {
         return Err(JsValue::from_str("Dimensions do not add up."));
     }
@@ -27,15 +34,159 @@ pub fn read_stats_from_screen(data: &[u8], width: u32, height: u32) -> Result<Js
         }
     }
 
-    let img_screen = DynamicImage::ImageRgba8(img_screen);
+    Ok(DynamicImage::ImageRgba8(img_screen))
+}
 
-    let scan_result = pkmn::utils::scan_img(img_screen);
+/// Creates the scan options, reading the calibrated region if provided.
+fn scan_options(calibration: Option<String>) -> Result<ScanOptions, JsValue> {
+    match calibration {
+        Some(text) => {
+            let calibration = Calibration::from_json(&text).map_err(|e| JsValue::from_str(&e))?;
+            Ok(ScanOptions::from_calibration(&calibration))
+        }
+        None => Ok(ScanOptions::default()),
+    }
+}
 
+/// Returns the result of a scan, with the line breaks of HTML.
+fn to_html(scan_result: Result<String, String>) -> JsValue {
     let text_output = match scan_result {
         Ok(text_output) => text_output,
         Err(error) => error,
     };
-    let text_output = text_output.replace("\n", "<br>");
+    JsValue::from_str(&text_output.replace("\n", "<br>"))
+}
+
+/// Locates the GameBoy, reads the contents of the summary screen 1
+/// and returns the stats of the found pokemon.
+///
+/// When the calibration is provided, the Game Boy is not located but read
+/// from the calibrated region. The screens are scanned within the session,
+/// e.g. the species of nicknamed Pokémon are known from their summaries.
+#[wasm_bindgen]
+pub fn read_stats_from_screen(
+    data: &[u8],
+    width: u32,
+    height: u32,
+    calibration: Option<String>,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let img_screen = image_from_pixel_data(data, width, height)?;
+    let options = scan_options(calibration)?;
+
+    let scan_result = SESSION.with(|session| {
+        pkmn::utils::scan_img_with_session(img_screen, &options, &mut session.borrow_mut())
+    });
+
+    Ok(to_html(scan_result))
+}
+
+/// Locates the GameBoy, reads the box list of Bill's PC and returns the box
+/// as far as it was scanned.
+///
+/// The scans of the box list are put together in the session.
+#[wasm_bindgen]
+pub fn read_box_from_screen(
+    data: &[u8],
+    width: u32,
+    height: u32,
+    calibration: Option<String>,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let img_screen = image_from_pixel_data(data, width, height)?;
+    let options = scan_options(calibration)?;
+
+    let scan_result = SESSION.with(|session| {
+        pkmn::utils::scan_box_with_session(img_screen, &options, &mut session.borrow_mut())
+    });
+
+    Ok(to_html(scan_result))
+}
+
+/// Locates the GameBoy, reads the bag and returns the details of the items.
+///
+/// The TMs and HMs are shown with the party members learning them, taken
+/// from the party menu scanned last in the session.
+#[wasm_bindgen]
+pub fn read_bag_from_screen(
+    data: &[u8],
+    width: u32,
+    height: u32,
+    calibration: Option<String>,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let img_screen = image_from_pixel_data(data, width, height)?;
+    let options = scan_options(calibration)?;
+
+    let scan_result = SESSION.with(|session| {
+        pkmn::utils::scan_bag_with_session(img_screen, &options, &session.borrow())
+    });
+
+    Ok(to_html(scan_result))
+}
+
+/// Returns the box of Bill's PC scanned last as JSON.
+#[wasm_bindgen]
+pub fn export_box_inventory() -> JsValue {
+    let json = SESSION.with(|session| session.borrow().box_inventory().to_json());
+    JsValue::from_str(&json)
+}
+
+/// Sets the badges of RBY, separated by commas, e.g. "Boulder, Soul".
+///
+/// The RBY trainer card can not be read for the badges, so they are given by
+/// the player. The badges boost the stats shown on the summary.
+#[wasm_bindgen]
+pub fn set_rby_badges(text: &str) -> Result<JsValue, JsValue> {
+    let badges = parse_badges(text, &RBY_BADGES).map_err(|e| JsValue::from_str(&e))?;
+    SESSION.with(|session| session.borrow_mut().record_badges(&RBY_BADGES, &badges));
+
+    let names = match badges.is_empty() {
+        true => "-".to_string(),
+        false => badges.join(", "),
+    };
+    Ok(JsValue::from_str(&format!(
+        "Badges: {names} ({}/8)",
+        badges.len()
+    )))
+}
+
+/// Forgets the screens scanned earlier, e.g. when switching games.
+#[wasm_bindgen]
+pub fn reset_session() {
+    SESSION.with(|session| *session.borrow_mut() = ScanSession::new());
+}
+
+/// Validates the user-selected region of the Game Boy screen.
+///
+/// Returns the calibration as JSON, to be passed to `read_stats_from_screen`.
+#[wasm_bindgen]
+pub fn calibrate_screen(
+    data: &[u8],
+    width: u32,
+    height: u32,
+    x: u32,
+    y: u32,
+    region_width: u32,
+    region_height: u32,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let img_screen = image_from_pixel_data(data, width, height)?;
+
+    let pos = Position {
+        x,
+        y,
+        width: region_width,
+        height: region_height,
+    };
+    let models = ScanOptions::default().aspect_models;
+
+    let calibration = pkmn::calibration::calibrate(&img_screen, &pos, &models)
+        .map_err(|err| JsValue::from_str(&err))?;
 
-    Ok(JsValue::from_str(&text_output))
+    Ok(JsValue::from_str(&calibration.to_json()))
 }
@@ -9,6 +9,12 @@ const button_scan = document.getElementById("button_scan");
 const button_interval_scan = document.getElementById("button_interval_scan");
 const button_canvas = document.getElementById("button_canvas");
 const button_video = document.getElementById("button_video");
+const button_region = document.getElementById("button_region");
+const button_reset_region = document.getElementById("button_reset_region");
+const button_reset_session = document.getElementById("button_reset_session");
+const button_export_box = document.getElementById("button_export_box");
+const button_badges = document.getElementById("button_badges");
+const select_mode = document.getElementById("select_mode");
 
 const text_output = document.getElementById("output");
 
@@ -40,6 +46,98 @@ button_video.addEventListener('click', function () {
     button_video.textContent = button_video.textContent === "Show screen" ? "Hide screen" : "Show screen";
 });
 
+button_region.onclick = function (e) {
+    startRegionSelection();
+};
+button_reset_region.onclick = function (e) {
+    localStorage.removeItem(CALIBRATION_KEY);
+    text_output.textContent = "The screen region was reset, the Game Boy will be located automatically.";
+};
+button_reset_session.onclick = function (e) {
+    wasm.reset_session();
+    text_output.textContent = "The scanned screens were forgotten, e.g. the species of nicknamed Pokémon.";
+};
+button_export_box.onclick = async function (e) {
+    await init();
+    const json = wasm.export_box_inventory();
+    const link = document.createElement("a");
+    link.href = URL.createObjectURL(new Blob([json], { type: "application/json" }));
+    link.download = "box.json";
+    link.click();
+    URL.revokeObjectURL(link.href);
+};
+button_badges.onclick = async function (e) {
+    const text = prompt("Badges of RBY, e.g. Boulder, Thunder, Soul:");
+    if (text === null) {
+        return;
+    }
+    await init();
+    try {
+        text_output.textContent = wasm.set_rby_badges(text);
+    } catch (error) {
+        text_output.textContent = error;
+    }
+};
+
+const CALIBRATION_KEY = "pkmn_calibration";
+
+let regionStart;  // Canvas coordinates where the region selection started
+
+// Returns the canvas pixel coordinates of the mouse event
+function canvasCoordinates(event) {
+    const rect = canvas.getBoundingClientRect();
+    const x = (event.clientX - rect.left) * canvas.width / rect.width;
+    const y = (event.clientY - rect.top) * canvas.height / rect.height;
+    return [Math.round(x), Math.round(y)];
+}
+
+// Takes a snapshot and lets the user drag a rectangle around the Game Boy screen
+async function startRegionSelection() {
+    await init();
+    drawSnapshot();
+    canvas.classList.remove('hidden');
+    button_canvas.textContent = "Hide snapshot";
+    text_output.textContent = "Drag a rectangle around the Game Boy screen on the snapshot below.";
+    regionStart = undefined;
+
+    canvas.onmousedown = function (e) {
+        regionStart = canvasCoordinates(e);
+    };
+    canvas.onmouseup = function (e) {
+        if (regionStart === undefined) {
+            return;
+        }
+        const [x0, y0] = regionStart;
+        const [x1, y1] = canvasCoordinates(e);
+        regionStart = undefined;
+        canvas.onmousedown = null;
+        canvas.onmouseup = null;
+
+        const x = Math.max(Math.min(x0, x1), 0);
+        const y = Math.max(Math.min(y0, y1), 0);
+        const width = Math.min(Math.abs(x1 - x0), canvas.width - x);
+        const height = Math.min(Math.abs(y1 - y0), canvas.height - y);
+
+        const pixelData = canvas.getContext('2d').getImageData(0, 0, canvas.width, canvas.height).data;
+        try {
+            const calibration = wasm.calibrate_screen(pixelData, canvas.width, canvas.height, x, y, width, height);
+            localStorage.setItem(CALIBRATION_KEY, calibration);
+            text_output.textContent = "The screen region was saved.";
+        } catch (error) {
+            text_output.textContent = error;
+        }
+    };
+}
+
+// Updates canvas from video, uses source size
+function drawSnapshot() {
+    let target_width = video.videoWidth;
+    let target_height = video.videoHeight;
+    canvas.width = target_width;
+    canvas.height = target_height;
+    canvas.getContext('2d').drawImage(video, 0, 0, target_width, target_height);
+}
+
 let intervalId;
 
 // Function to toggle the periodic calling
@@ -76,22 +174,28 @@ async function gameboy() {
     // Instantiate the WebAssembly module
     await init();
 
-    // Update canvas from video, use source size
-    let target_width = video.videoWidth;
-    let target_height = video.videoHeight;
-    canvas.width = target_width;
-    canvas.height = target_height;
-    canvas.getContext('2d').drawImage(video, 0, 0, target_width, target_height);
+    drawSnapshot();
 
     // Get ImageData of whole canvas
-    let imageData = canvas.getContext('2d').getImageData(0, 0, target_width, target_height);
+    let imageData = canvas.getContext('2d').getImageData(0, 0, canvas.width, canvas.height);
     let pixelData = imageData.data;
 
+    // The user-selected screen region, if any
+    let calibration = localStorage.getItem(CALIBRATION_KEY) ?? undefined;
+
     const t0 = performance.now();
 
+    // The lists of Bill's PC and the bag show no static text, so they are scanned on request
+    const scans = {
+        screens: wasm.read_stats_from_screen,
+        box: wasm.read_box_from_screen,
+        bag: wasm.read_bag_from_screen,
+    };
+    const scan = scans[select_mode.value];
+
     // Try locating the GameBoy
     try {
-        var results = wasm.read_stats_from_screen(pixelData, target_width, target_height);
+        var results = scan(pixelData, canvas.width, canvas.height, calibration);
         text_output.innerHTML = results;
     } catch (error) {
         text_output.textContent = error;